@@ -1,5 +1,4 @@
 #![feature(test)]
-#![cfg(any(feature = "v2", feature = "v3", feature = "v5",))]
 
 extern crate test;
 use test::Bencher;
@@ -30,3 +29,25 @@ fn new_uuid_v4(b: &mut Bencher) {
 fn new_uuid_v5(b: &mut Bencher) {
     b.iter(|| UUID::v5("bench", UUID::NAMESPACE_X500).new());
 }
+
+#[bench]
+fn new_uuid_v6(b: &mut Bencher) {
+    b.iter(|| UUID::v6().new());
+}
+
+#[bench]
+fn new_uuid_v7(b: &mut Bencher) {
+    b.iter(|| UUID::v7().new());
+}
+
+#[bench]
+fn fill_uuid_v4(b: &mut Bencher) {
+    let mut uuids = [UUID::NIL; 1_000];
+    b.iter(|| UUID::fill_v4(&mut uuids));
+}
+
+#[bench]
+fn fill_uuid_v7(b: &mut Bencher) {
+    let mut uuids = [UUID::NIL; 1_000];
+    b.iter(|| UUID::fill_v7(&mut uuids));
+}
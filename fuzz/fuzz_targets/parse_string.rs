@@ -1,10 +1,14 @@
 #![no_main]
 use libfuzzer_sys::fuzz_target;
 
+use std::str::FromStr;
+
 use unik::*;
 
-fuzz_target!(|data: [u8; 16]| {
-    if let Ok(s) = std::str::from_utf8(&data) {
-        let _ = UUID::from_str(s);
+fuzz_target!(|data: &[u8]| {
+    if let Ok(s) = std::str::from_utf8(data) {
+        if let Ok(uuid) = UUID::from_str(s) {
+            assert_eq!(UUID::parse(&uuid.to_string()), Ok(uuid));
+        }
     }
 });
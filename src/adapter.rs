@@ -0,0 +1,247 @@
+//! Adapters to format a [`UUID`] in each of its textual [`Format`]s.
+//!
+//! Every adapter implements [`Display`](fmt::Display), [`LowerHex`](fmt::LowerHex)
+//! and [`UpperHex`](fmt::UpperHex), and can `encode` into a caller-supplied
+//! buffer without allocating.
+
+use core::fmt;
+
+use crate::{Format, UUID};
+
+const LOWER: &[u8; 16] = b"0123456789abcdef";
+const UPPER: &[u8; 16] = b"0123456789ABCDEF";
+
+// Method names must be visible outside the expansion, which rules out `macro`'s hygiene.
+macro_rules! adapter {
+    ($(#[$meta:meta])* $name:ident, $format:expr, $len:expr, $method:ident) => {
+        $(#[$meta])*
+        #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+        pub struct $name {
+            uuid: UUID,
+            upper: bool,
+        }
+
+        impl $name {
+            /// Length of the encoded string, in bytes.
+            pub const LENGTH: usize = $len;
+
+            /// Formats hex digits in uppercase.
+            pub const fn upper(self) -> Self {
+                Self { upper: true, ..self }
+            }
+
+            /// Formats hex digits in lowercase, the default.
+            pub const fn lower(self) -> Self {
+                Self { upper: false, ..self }
+            }
+
+            /// Returns the adapted [`UUID`].
+            pub const fn as_uuid(&self) -> &UUID {
+                &self.uuid
+            }
+
+            /// Writes the [`UUID`] at the start of `buf` and returns it as a string.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `buf` is shorter than [`Self::LENGTH`].
+            pub fn encode<'b>(&self, buf: &'b mut [u8]) -> &'b mut str {
+                encode(&self.uuid, buf, $format, self.upper)
+            }
+        }
+
+        impl UUID {
+            #[doc = concat!("Returns a [`", stringify!($name), "`] adapter to format this [`UUID`].")]
+            pub const fn $method(self) -> $name {
+                $name {
+                    uuid: self,
+                    upper: false,
+                }
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt.pad(self.encode(&mut [0u8; $len]))
+            }
+        }
+
+        impl fmt::LowerHex for $name {
+            fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Display::fmt(&self.lower(), fmt)
+            }
+        }
+
+        impl fmt::UpperHex for $name {
+            fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Display::fmt(&self.upper(), fmt)
+            }
+        }
+
+        impl From<UUID> for $name {
+            fn from(uuid: UUID) -> Self {
+                uuid.$method()
+            }
+        }
+    };
+}
+
+adapter!(
+    /// Formats a [`UUID`] as 32 hex digits, like `67e5504410b1426f9247bb680e5fe0c8`.
+    Simple,
+    Format::Simple,
+    32,
+    simple
+);
+
+adapter!(
+    /// Formats a [`UUID`] as hex digits in five groups separated by hyphens,
+    /// like `67e55044-10b1-426f-9247-bb680e5fe0c8`.
+    Hyphenated,
+    Format::Hyphenated,
+    36,
+    hyphenated
+);
+
+adapter!(
+    /// Formats a [`UUID`] as hyphenated and surrounded by braces,
+    /// like `{67e55044-10b1-426f-9247-bb680e5fe0c8}`.
+    Braced,
+    Format::Braced,
+    38,
+    braced
+);
+
+adapter!(
+    /// Formats a [`UUID`] as hyphenated and prefixed by `urn:uuid:`,
+    /// like `urn:uuid:67e55044-10b1-426f-9247-bb680e5fe0c8`.
+    Urn,
+    Format::Urn,
+    45,
+    urn
+);
+
+fn encode<'b>(uuid: &UUID, buf: &'b mut [u8], format: Format, upper: bool) -> &'b mut str {
+    let (prefix, suffix): (&[u8], &[u8]) = match format {
+        Format::Simple | Format::Hyphenated => (b"", b""),
+        Format::Braced => (b"{", b"}"),
+        Format::Urn => (b"urn:uuid:", b""),
+    };
+    let hyphenated = format != Format::Simple;
+    let len = prefix.len() + 32 + if hyphenated { 4 } else { 0 } + suffix.len();
+
+    assert!(
+        buf.len() >= len,
+        "buffer too small: expected at least {} bytes, found {}",
+        len,
+        buf.len()
+    );
+    let buf = &mut buf[..len];
+    let digits = if upper { UPPER } else { LOWER };
+
+    buf[..prefix.len()].copy_from_slice(prefix);
+    let mut i = prefix.len();
+    for (n, byte) in uuid.0.iter().enumerate() {
+        if hyphenated && matches!(n, 4 | 6 | 8 | 10) {
+            buf[i] = b'-';
+            i += 1;
+        }
+        buf[i] = digits[(byte >> 4) as usize];
+        buf[i + 1] = digits[(byte & 0xf) as usize];
+        i += 2;
+    }
+    buf[i..].copy_from_slice(suffix);
+
+    core::str::from_utf8_mut(buf).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ID: UUID = UUID([
+        0x67, 0xe5, 0x50, 0x44, 0x10, 0xb1, 0x42, 0x6f, 0x92, 0x47, 0xbb, 0x68, 0x0e, 0x5f, 0xe0,
+        0xc8,
+    ]);
+
+    #[test]
+    fn format_adapters() {
+        assert_eq!(ID.simple().to_string(), "67e5504410b1426f9247bb680e5fe0c8");
+        assert_eq!(
+            ID.hyphenated().to_string(),
+            "67e55044-10b1-426f-9247-bb680e5fe0c8"
+        );
+        assert_eq!(
+            ID.braced().to_string(),
+            "{67e55044-10b1-426f-9247-bb680e5fe0c8}"
+        );
+        assert_eq!(
+            ID.urn().to_string(),
+            "urn:uuid:67e55044-10b1-426f-9247-bb680e5fe0c8"
+        );
+        assert_eq!(
+            ID.hyphenated().upper().to_string(),
+            "67E55044-10B1-426F-9247-BB680E5FE0C8"
+        );
+        assert_eq!(
+            ID.urn().upper().to_string(),
+            "urn:uuid:67E55044-10B1-426F-9247-BB680E5FE0C8"
+        );
+        assert_eq!(ID.simple().upper().lower(), ID.simple());
+    }
+
+    #[test]
+    fn format_hex() {
+        assert_eq!(format!("{:x}", ID), "67e55044-10b1-426f-9247-bb680e5fe0c8");
+        assert_eq!(format!("{:X}", ID), "67E55044-10B1-426F-9247-BB680E5FE0C8");
+        assert_eq!(
+            format!("{:X}", ID.simple()),
+            "67E5504410B1426F9247BB680E5FE0C8"
+        );
+        assert_eq!(
+            format!("{:x}", ID.braced().upper()),
+            "{67e55044-10b1-426f-9247-bb680e5fe0c8}"
+        );
+        assert_eq!(
+            format!("{:>40}", ID.braced()),
+            "  {67e55044-10b1-426f-9247-bb680e5fe0c8}"
+        );
+    }
+
+    #[test]
+    fn encode_into_buffer() {
+        let mut buf = [b'!'; 64];
+        assert_eq!(
+            ID.braced().upper().encode(&mut buf),
+            "{67E55044-10B1-426F-9247-BB680E5FE0C8}"
+        );
+        assert_eq!(buf[Braced::LENGTH], b'!');
+
+        let mut buf = [0u8; Simple::LENGTH];
+        assert_eq!(
+            ID.simple().encode(&mut buf),
+            "67e5504410b1426f9247bb680e5fe0c8"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "buffer too small")]
+    fn encode_into_short_buffer() {
+        ID.urn().encode(&mut [0u8; Hyphenated::LENGTH]);
+    }
+
+    #[test]
+    fn format_parse_round_trip() {
+        let mut buf = [0u8; Urn::LENGTH];
+        let encoded = [
+            (Format::Simple, ID.simple().encode(&mut buf).to_string()),
+            (Format::Hyphenated, ID.hyphenated().upper().to_string()),
+            (Format::Braced, ID.braced().to_string()),
+            (Format::Urn, ID.urn().upper().to_string()),
+        ];
+
+        for (format, us) in encoded {
+            assert_eq!(UUID::parse_only(&us, &[format]), Ok(ID));
+        }
+    }
+}
@@ -0,0 +1,122 @@
+//! Sources of time for the time-based generators.
+//!
+//! [`UUID::v1`](crate::UUID::v1), [`UUID::v6`](crate::UUID::v6) and
+//! `UUID::v7` read the [`SystemClock`] by default. Building a
+//! [`Generator`](crate::Generator) with another [`Clock`] makes them
+//! reproducible, e.g. in snapshot tests.
+
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime},
+};
+
+use crate::Timestamp;
+
+/// A source of [`Timestamp`]s.
+pub trait Clock: Send + Sync {
+    /// Returns the current time.
+    fn now(&self) -> Timestamp;
+}
+
+impl<C: Clock + ?Sized> Clock for Arc<C> {
+    fn now(&self) -> Timestamp {
+        (**self).now()
+    }
+}
+
+/// Reads the time from the operating system.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Timestamp {
+        SystemTime::now().into()
+    }
+}
+
+/// A clock that only moves when told to.
+#[derive(Debug)]
+pub struct MockClock {
+    now: Mutex<Timestamp>,
+}
+
+impl MockClock {
+    /// Returns a [`MockClock`] starting at `start`.
+    pub fn new(start: Timestamp) -> Self {
+        Self {
+            now: Mutex::new(start),
+        }
+    }
+
+    /// Moves the clock to `now`, which may be in the past.
+    pub fn set(&self, now: Timestamp) {
+        *self.now.lock().unwrap() = now;
+    }
+
+    /// Moves the clock forward by `duration`.
+    pub fn advance(&self, duration: Duration) {
+        *self.now.lock().unwrap() += duration;
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> Timestamp {
+        *self.now.lock().unwrap()
+    }
+}
+
+/// A clock that is stopped at the given [`Timestamp`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct FixedClock(pub Timestamp);
+
+impl Clock for FixedClock {
+    fn now(&self) -> Timestamp {
+        self.0
+    }
+}
+
+/// Makes the global [`Generator`](crate::Generator) read the time from `clock`.
+///
+/// Identifiers stay unique when `clock` stops or goes backwards, at the cost of
+/// a clock sequence increment for versions 1 and 6, and of a timestamp borrowed
+/// from the future for version 7.
+///
+/// The clock is swapped for the whole process, including tests running in
+/// parallel on other threads. Prefer a [`Generator`](crate::Generator) built
+/// with [`GeneratorBuilder::clock`](crate::GeneratorBuilder::clock) where possible.
+#[cfg(feature = "test-util")]
+pub fn set_clock<C: Clock + 'static>(clock: C) {
+    crate::Generator::global().set_clock(Arc::new(clock));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mock_clock_moves_when_told() {
+        let clock = MockClock::new(Timestamp::UNIX_EPOCH);
+        assert_eq!(clock.now(), Timestamp::UNIX_EPOCH);
+        assert_eq!(clock.now(), Timestamp::UNIX_EPOCH);
+
+        clock.advance(Duration::from_millis(1_500));
+        assert_eq!(clock.now(), Timestamp::from_unix(1, 500_000_000));
+
+        clock.set(Timestamp::from_unix(-1, 0));
+        assert_eq!(clock.now(), Timestamp::from_unix(-1, 0));
+    }
+
+    #[test]
+    fn clocks_behind_arc() {
+        let mock = Arc::new(MockClock::new(Timestamp::UNIX_EPOCH));
+        let shared: Arc<dyn Clock> = Arc::new(Arc::clone(&mock));
+        mock.advance(Duration::from_secs(1));
+        assert_eq!(shared.now(), Timestamp::from_unix(1, 0));
+
+        let fixed = FixedClock(Timestamp::from_unix(42, 0));
+        assert_eq!(fixed.now(), fixed.now());
+
+        let before = Timestamp::now();
+        assert!(SystemClock.now() >= before);
+    }
+}
@@ -0,0 +1,444 @@
+use std::{
+    io,
+    sync::{Arc, LazyLock, Mutex},
+};
+
+#[cfg(feature = "rand")]
+use rand_core::{CryptoRng, RngCore};
+
+use crate::{
+    clock::{Clock, SystemClock},
+    rfc4122::{v1, v6},
+    state::{Context, Storage},
+    ClockSeq, Layout, Node,
+};
+#[cfg(feature = "rand")]
+use crate::{
+    rfc4122::{v4, v7},
+    UUID,
+};
+
+/// The instance behind `UUID::v1`, `UUID::v6` and the other free functions.
+static GLOBAL: LazyLock<Generator> = LazyLock::new(Generator::new);
+
+#[cfg(feature = "test-util")]
+thread_local! {
+    /// Overrides [`GLOBAL`] on the current thread, see [`SeededGenerator::set_default`](crate::SeededGenerator::set_default).
+    static CURRENT: std::cell::RefCell<Option<Arc<Generator>>> = const { std::cell::RefCell::new(None) };
+}
+
+/// Generates [`UUID`](crate::UUID)s from its own node, clock, random generator and clock
+/// sequence storage, see [`GeneratorBuilder`].
+///
+/// All methods take `&self`, so that one [`Generator`] can be shared between
+/// threads. Identifiers of the same [`Generator`] are unique and, for version 7,
+/// strictly increasing. Version 6 identifiers increase as long as the clock does:
+/// when it goes backwards, the clock sequence is incremented and the next ones
+/// sort before the previous ones.
+pub struct Generator {
+    pub(crate) v1: Mutex<Context>,
+    #[cfg(feature = "rand")]
+    pub(crate) v7: Mutex<v7::Context>,
+    #[cfg(feature = "rand")]
+    rng: Option<Mutex<Box<dyn RngCore + Send>>>,
+}
+
+impl Generator {
+    /// Returns a [`Generator`] configured like the process-global one.
+    pub fn new() -> Self {
+        GeneratorBuilder::default().finish()
+    }
+
+    /// Returns a [`GeneratorBuilder`] to configure a new [`Generator`].
+    pub fn builder() -> GeneratorBuilder {
+        GeneratorBuilder::default()
+    }
+
+    /// Returns the process-global [`Generator`] the free functions delegate to.
+    ///
+    /// It reads the [`SystemClock`] and the default [`Node`], see
+    /// [`set_storage`](crate::state::set_storage) to save its state.
+    pub fn global() -> &'static Generator {
+        &GLOBAL
+    }
+
+    /// Runs `f` with the [`Generator`] the free functions delegate to on the current thread.
+    pub(crate) fn with_current<T>(f: impl FnOnce(&Generator) -> T) -> T {
+        #[cfg(feature = "test-util")]
+        if let Some(current) = CURRENT.with(|current| current.borrow().clone()) {
+            return f(&current);
+        }
+        f(&GLOBAL)
+    }
+
+    /// Makes the free functions delegate to `generator` on the current thread,
+    /// or to the global one if `None`, and returns the previous override.
+    #[cfg(feature = "test-util")]
+    pub(crate) fn set_current(generator: Option<Arc<Generator>>) -> Option<Arc<Generator>> {
+        CURRENT.with(|current| current.replace(generator))
+    }
+
+    /// Returns the [`Node`] stamped into versions 1 and 6.
+    pub fn node(&self) -> Node {
+        self.v1.lock().unwrap().node()
+    }
+
+    /// See [`UUID::v1`](crate::UUID::v1).
+    ///
+    /// When the [`Storage`] fails, carries on from the state held in memory,
+    /// see [`Generator::try_v1`].
+    pub fn v1(&self) -> Layout {
+        let mut ctx = self.v1.lock().unwrap();
+        let (ticks, cs) = ctx.tick();
+
+        v1::build(ticks, cs.to_be_bytes(), ctx.node())
+    }
+
+    /// Like [`Generator::v1`], but returns the error of the [`Storage`].
+    pub fn try_v1(&self) -> io::Result<Layout> {
+        let mut ctx = self.v1.lock().unwrap();
+        let (ticks, cs) = ctx.try_tick()?;
+
+        Ok(v1::build(ticks, cs.to_be_bytes(), ctx.node()))
+    }
+
+    /// See [`UUID::v6`](crate::UUID::v6).
+    ///
+    /// When the [`Storage`] fails, carries on from the state held in memory,
+    /// see [`Generator::try_v6`].
+    pub fn v6(&self) -> Layout {
+        let mut ctx = self.v1.lock().unwrap();
+        let (ticks, cs) = ctx.tick();
+
+        v6::build(ticks, cs.to_be_bytes(), ctx.node())
+    }
+
+    /// Like [`Generator::v6`], but returns the error of the [`Storage`].
+    pub fn try_v6(&self) -> io::Result<Layout> {
+        let mut ctx = self.v1.lock().unwrap();
+        let (ticks, cs) = ctx.try_tick()?;
+
+        Ok(v6::build(ticks, cs.to_be_bytes(), ctx.node()))
+    }
+
+    /// See [`UUID::v4`].
+    #[cfg(feature = "rand")]
+    pub fn v4(&self) -> Layout {
+        v4::build(self.random_bytes())
+    }
+
+    /// See [`UUID::v7`].
+    #[cfg(feature = "rand")]
+    pub fn v7(&self) -> Layout {
+        let rand = self.random_bytes();
+        let (millis, counter) = self
+            .v7
+            .lock()
+            .unwrap()
+            .tick(u16::from_le_bytes([rand[0], rand[1]]));
+
+        v7::build(millis, counter, rand)
+    }
+
+    /// See [`UUID::fill_v4`].
+    #[cfg(feature = "rand")]
+    pub fn fill_v4(&self, uuids: &mut [UUID]) {
+        self.fill_random(uuids);
+        for uuid in uuids {
+            *uuid = v4::build(uuid.0).new();
+        }
+    }
+
+    /// See [`UUID::fill_v7`].
+    #[cfg(feature = "rand")]
+    pub fn fill_v7(&self, uuids: &mut [UUID]) {
+        self.fill_random(uuids);
+
+        let mut ctx = self.v7.lock().unwrap();
+        let now = ctx.clock.now().to_unix_millis();
+        for uuid in uuids {
+            let rand = uuid.0;
+            let (millis, counter) = ctx.next(now, u16::from_le_bytes([rand[0], rand[1]]));
+            *uuid = v7::build(millis, counter, rand).new();
+        }
+    }
+
+    /// Makes versions 1, 6 and 7 read the time from `clock`.
+    #[cfg(feature = "test-util")]
+    pub(crate) fn set_clock(&self, clock: Arc<dyn Clock>) {
+        #[cfg(feature = "rand")]
+        {
+            self.v7.lock().unwrap().clock = Arc::clone(&clock);
+        }
+        self.v1.lock().unwrap().clock = clock;
+    }
+
+    #[cfg(feature = "rand")]
+    pub(crate) fn random_bytes(&self) -> [u8; 16] {
+        let mut rand = [UUID::NIL];
+        self.fill_random(&mut rand);
+        rand[0].0
+    }
+
+    /// Overwrites `uuids` with random bits, borrowing the random generator only once.
+    #[cfg(feature = "rand")]
+    fn fill_random(&self, uuids: &mut [UUID]) {
+        let bufs = uuids.iter_mut().map(|uuid| &mut uuid.0);
+        match &self.rng {
+            Some(rng) => {
+                let mut rng = rng.lock().unwrap();
+                bufs.for_each(|buf| rng.fill_bytes(buf));
+            }
+            None => v4::fill_random(bufs),
+        }
+    }
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Configures a [`Generator`].
+///
+/// Anything left unset behaves like the process-global [`Generator`].
+#[derive(Default)]
+pub struct GeneratorBuilder {
+    node: Option<Node>,
+    clock: Option<Arc<dyn Clock>>,
+    #[cfg(feature = "rand")]
+    rng: Option<Box<dyn RngCore + Send>>,
+    clock_seq: Option<u16>,
+    storage: Option<Box<dyn Storage>>,
+}
+
+impl GeneratorBuilder {
+    /// Sets the [`Node`] stamped into versions 1 and 6.
+    ///
+    /// Defaults to [`Node::default`], looked up when the first version 1 or 6 identifier is built.
+    pub fn node(mut self, node: Node) -> Self {
+        self.node = Some(node);
+        self
+    }
+
+    /// Sets the [`Clock`] read by versions 1, 6 and 7.
+    pub fn clock<C: Clock + 'static>(mut self, clock: C) -> Self {
+        self.clock = Some(Arc::new(clock));
+        self
+    }
+
+    /// Sets the random generator used by versions 4 and 7, and to pick the initial
+    /// clock sequence. Defaults to the [`ThreadRng`](crate::rfc4122::v4::ThreadRng).
+    #[cfg(feature = "rand")]
+    pub fn rng<R: RngCore + CryptoRng + Send + 'static>(mut self, rng: R) -> Self {
+        self.rng = Some(Box::new(rng));
+        self
+    }
+
+    /// Sets the [`FastRng`](crate::rfc4122::v4::FastRng) as the random generator, see
+    /// [`GeneratorBuilder::rng`]. It is faster, but not cryptographically secure.
+    #[cfg(feature = "fast-rng")]
+    pub fn fast_rng(self) -> Self {
+        self.any_rng(v4::FastRng)
+    }
+
+    /// Like [`GeneratorBuilder::rng`], without requiring a cryptographically secure generator.
+    #[cfg(any(feature = "test-util", feature = "fast-rng"))]
+    pub(crate) fn any_rng<R: RngCore + Send + 'static>(mut self, rng: R) -> Self {
+        self.rng = Some(Box::new(rng));
+        self
+    }
+
+    /// Sets the initial clock sequence, instead of drawing it from the random generator.
+    #[cfg(feature = "test-util")]
+    pub(crate) fn clock_seq(mut self, clock_seq: u16) -> Self {
+        self.clock_seq = Some(clock_seq);
+        self
+    }
+
+    /// Sets where versions 1 and 6 save their state, see [`state`](crate::state).
+    pub fn storage<S: Storage + 'static>(mut self, storage: S) -> Self {
+        self.storage = Some(Box::new(storage));
+        self
+    }
+
+    /// Returns the configured [`Generator`], after loading the state saved in its storage if any.
+    pub fn build(mut self) -> io::Result<Generator> {
+        let storage = self.storage.take();
+        let generator = self.finish();
+
+        if let Some(storage) = storage {
+            generator.v1.lock().unwrap().set_storage(storage)?;
+        }
+        Ok(generator)
+    }
+
+    /// Returns the configured [`Generator`], ignoring the storage.
+    pub(crate) fn finish(self) -> Generator {
+        let clock = self.clock.unwrap_or_else(|| Arc::new(SystemClock));
+
+        #[cfg(feature = "rand")]
+        let mut rng = self.rng;
+        #[cfg(feature = "rand")]
+        let clock_seq = match (self.clock_seq, &mut rng) {
+            (Some(clock_seq), _) => clock_seq,
+            (None, Some(rng)) => rng.next_u32() as u16,
+            (None, None) => ClockSeq::new().get(),
+        };
+        #[cfg(not(feature = "rand"))]
+        let clock_seq = self.clock_seq.unwrap_or_else(|| ClockSeq::new().get());
+
+        let mut v1 = Context::new(self.node, clock_seq);
+        v1.clock = Arc::clone(&clock);
+
+        Generator {
+            v1: Mutex::new(v1),
+            #[cfg(feature = "rand")]
+            v7: Mutex::new(v7::Context::new(clock)),
+            #[cfg(feature = "rand")]
+            rng: rng.map(Mutex::new),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{clock::FixedClock, Timestamp, Version};
+
+    const NODE: Node = Node([0x9f, 0x6b, 0xde, 0xce, 0xd8, 0x46]);
+    const START: Timestamp = Timestamp::from_unix(1_645_557_742, 0);
+
+    #[test]
+    fn generator_from_builder() {
+        let generator = Generator::builder()
+            .node(NODE)
+            .clock(FixedClock(START))
+            .build()
+            .unwrap();
+        assert_eq!(generator.node(), NODE);
+
+        let v1 = generator.v1().new();
+        let v6 = generator.v6().new();
+        assert_eq!(v1.get_version(), Ok(Version::TIME));
+        assert_eq!(v1.get_node(), NODE);
+        assert_eq!(v1.get_timestamp(), Some(START));
+        // The clock is stopped, the next tick is simulated.
+        assert_eq!(
+            v6.get_timestamp(),
+            Some(START + std::time::Duration::from_nanos(100))
+        );
+    }
+
+    #[test]
+    fn generators_are_independent() {
+        let a = Generator::builder().node(NODE).build().unwrap();
+        let b = Generator::builder().node(Node([0x01; 6])).build().unwrap();
+
+        assert_eq!(a.v1().new().get_node(), NODE);
+        assert_eq!(b.v1().new().get_node(), Node([0x01; 6]));
+        assert_ne!(Generator::global().node(), NODE);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn generator_looks_up_node_on_first_use() {
+        let generator = Generator::new();
+        generator.v4();
+        generator.v7();
+        generator.fill_v4(&mut [UUID::NIL; 4]);
+        assert_eq!(generator.v1.lock().unwrap().node, None);
+
+        let uuid = generator.v1().new();
+        assert_eq!(generator.v1.lock().unwrap().node, Some(uuid.get_node()));
+        assert_eq!(generator.v6().new().get_node(), uuid.get_node());
+    }
+
+    #[test]
+    fn generator_with_storage() {
+        let path = std::env::temp_dir().join(format!("unik-generator-{}", std::process::id()));
+        let build = || {
+            Generator::builder()
+                .node(NODE)
+                .clock(FixedClock(START))
+                .storage(crate::state::FileStorage::new(&path))
+                .build()
+                .unwrap()
+        };
+
+        let first = build().v1().new();
+        assert_eq!(first.get_timestamp(), Some(START));
+        // Restarted with the same stopped clock, the next tick is simulated.
+        let second = build().v1().new();
+        assert_eq!(
+            second.get_timestamp(),
+            Some(START + std::time::Duration::from_nanos(100))
+        );
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn generator_reports_storage_errors() {
+        // A directory cannot be opened as the storage file.
+        let generator = Generator::builder()
+            .node(NODE)
+            .clock(FixedClock(START))
+            .build()
+            .unwrap();
+        generator
+            .v1
+            .lock()
+            .unwrap()
+            .set_storage(Box::new(crate::state::FileStorage::new(
+                std::env::temp_dir(),
+            )))
+            .unwrap_err();
+
+        assert!(generator.try_v1().is_err());
+        assert!(generator.try_v6().is_err());
+        assert_eq!(generator.v1().new().get_timestamp(), Some(START));
+    }
+
+    #[cfg(feature = "fast-rng")]
+    #[test]
+    fn generator_with_fast_rng() {
+        let generator = Generator::builder().fast_rng().build().unwrap();
+        let (a, b) = (generator.v4().new(), generator.v4().new());
+        assert_eq!(a.get_version(), Ok(Version::RAND));
+        assert_ne!(a, b);
+
+        let mut uuids = [UUID::NIL; 100];
+        generator.fill_v7(&mut uuids);
+        assert!(uuids.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn generator_with_rng() {
+        use rand_chacha::ChaCha12Rng;
+        use rand_core::SeedableRng;
+
+        let build = || {
+            Generator::builder()
+                .node(NODE)
+                .clock(FixedClock(START))
+                .rng(ChaCha12Rng::seed_from_u64(42))
+                .build()
+                .unwrap()
+        };
+        let (a, b) = (build(), build());
+        for _ in 0..100 {
+            assert_eq!(a.v4().new(), b.v4().new());
+            assert_eq!(a.v7().new(), b.v7().new());
+            assert_eq!(a.v1().new(), b.v1().new());
+        }
+
+        let mut uuids = [UUID::NIL; 100];
+        a.fill_v7(&mut uuids);
+        assert!(uuids.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(a.v7().new() > uuids[99]);
+        assert_eq!(uuids[0].get_timestamp(), Some(START));
+    }
+}
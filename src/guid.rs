@@ -0,0 +1,126 @@
+use core::{fmt, str::FromStr};
+
+use crate::{ParseError, UUID};
+
+impl UUID {
+    /// Creates [`UUID`] from bytes in Microsoft GUID order, where `time_low`,
+    /// `time_mid` and `time_hi_version` are stored least significant byte first.
+    ///
+    /// This is the layout of `uniqueidentifier` columns and of the Win32 `GUID`
+    /// struct on little-endian machines.
+    pub const fn from_bytes_le(b: [u8; 16]) -> UUID {
+        UUID(swap(b))
+    }
+
+    /// Returns the bytes in Microsoft GUID order, see [`UUID::from_bytes_le`].
+    pub const fn to_bytes_le(&self) -> [u8; 16] {
+        swap(self.0)
+    }
+
+    /// Returns a [`Guid`] view of this [`UUID`].
+    pub const fn as_guid(&self) -> Guid {
+        Guid(*self)
+    }
+}
+
+/// Swaps the byte order of the first three fields, which is its own inverse.
+const fn swap(b: [u8; 16]) -> [u8; 16] {
+    [
+        b[3], b[2], b[1], b[0], b[5], b[4], b[7], b[6], b[8], b[9], b[10], b[11], b[12], b[13],
+        b[14], b[15],
+    ]
+}
+
+/// A [`UUID`] viewed as a .NET `System.Guid`.
+///
+/// Strings are the same for both, bytes follow `Guid.ToByteArray()` and the
+/// `Guid(byte[])` constructor.
+#[derive(Default, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct Guid(pub UUID);
+
+impl Guid {
+    /// Creates [`Guid`] from the output of `Guid.ToByteArray()`.
+    pub const fn from_bytes(bytes: [u8; 16]) -> Guid {
+        Guid(UUID::from_bytes_le(bytes))
+    }
+
+    /// Returns the same bytes as `Guid.ToByteArray()`.
+    pub const fn to_bytes(&self) -> [u8; 16] {
+        self.0.to_bytes_le()
+    }
+
+    /// Returns the viewed [`UUID`].
+    pub const fn as_uuid(&self) -> &UUID {
+        &self.0
+    }
+}
+
+impl From<UUID> for Guid {
+    fn from(uuid: UUID) -> Self {
+        Guid(uuid)
+    }
+}
+
+impl From<Guid> for UUID {
+    fn from(guid: Guid) -> Self {
+        guid.0
+    }
+}
+
+impl fmt::Display for Guid {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, fmt)
+    }
+}
+
+impl FromStr for Guid {
+    type Err = ParseError;
+
+    fn from_str(us: &str) -> Result<Self, Self::Err> {
+        UUID::parse(us).map(Guid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::uuid;
+
+    // From the documentation of `Guid.ToByteArray()`.
+    const ID: UUID = uuid!("35918bc9-196d-40ea-9779-889d79b753f0");
+    const BYTES: [u8; 16] = [
+        0xc9, 0x8b, 0x91, 0x35, 0x6d, 0x19, 0xea, 0x40, 0x97, 0x79, 0x88, 0x9d, 0x79, 0xb7, 0x53,
+        0xf0,
+    ];
+
+    #[test]
+    fn uuid_mixed_endian() {
+        assert_eq!(ID.to_bytes_le(), BYTES);
+        assert_eq!(UUID::from_bytes_le(BYTES), ID);
+        assert_eq!(UUID::from_bytes_le(ID.to_bytes_le()), ID);
+        assert_eq!(
+            UUID::from(BYTES).to_string(),
+            "c98b9135-6d19-ea40-9779-889d79b753f0"
+        );
+    }
+
+    #[test]
+    fn guid_round_trip() {
+        let guid = Guid::from_bytes(BYTES);
+        assert_eq!(guid.to_bytes(), BYTES);
+        assert_eq!(*guid.as_uuid(), ID);
+        assert_eq!(ID.as_guid(), guid);
+        assert_eq!(UUID::from(guid), ID);
+
+        // `new Guid("00112233-4455-6677-8899-aabbccddeeff").ToByteArray()`.
+        let guid: Guid = "{00112233-4455-6677-8899-AABBCCDDEEFF}".parse().unwrap();
+        assert_eq!(
+            guid.to_bytes(),
+            [
+                0x33, 0x22, 0x11, 0x00, 0x55, 0x44, 0x77, 0x66, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd,
+                0xee, 0xff
+            ]
+        );
+        assert_eq!(guid.to_string(), "00112233-4455-6677-8899-aabbccddeeff");
+    }
+}
@@ -9,17 +9,33 @@
 #![doc(html_root_url = "https://docs.rs/unik")]
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
 #![feature(decl_macro)]
-#![feature(box_syntax)]
-#![feature(strict_provenance)]
 
+pub mod adapter;
+pub mod clock;
+mod generator;
+mod guid;
+mod parser;
 pub mod rfc4122;
+#[cfg(feature = "test-util")]
+mod seeded;
+#[cfg(feature = "serde")]
+pub mod serde;
+pub mod state;
+mod timestamp;
 
-use core::fmt;
-use std::{convert, sync::Mutex};
+use core::{array, fmt};
+use std::{borrow, convert, sync::Mutex};
 
 #[cfg(feature = "utc")]
 pub use chrono::Utc;
 
+pub use generator::{Generator, GeneratorBuilder};
+pub use guid::Guid;
+pub use parser::{uuid, Format, ParseError};
+#[cfg(feature = "test-util")]
+pub use seeded::{DefaultGuard, SeededGenerator};
+pub use timestamp::{OutOfRangeError, Timestamp};
+
 /// The `IEEE-802` network address.
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub struct Node(pub [u8; 6]);
@@ -38,19 +54,16 @@ impl std::default::Default for Node {
     #[allow(unreachable_code)]
     fn default() -> Self {
         #[cfg(feature = "mac")]
-        return Self::from(Node(
-            mac_address::get_mac_address().unwrap().unwrap().bytes(),
-        ));
+        return Node(mac_address::get_mac_address().unwrap().unwrap().bytes());
 
+        // A random node has its multicast bit set, see `rfc4122` section 4.5.
         #[cfg(feature = "rand")]
         {
-            use nanorand::Rng;
-            let buf: [u8; 6] = [0u8; 6];
-            nanorand::ChaCha::<6>::new().fill_bytes(buf);
-            return Self::from(Node(buf));
+            let rand = rfc4122::v4::get_random().to_le_bytes();
+            return Node([rand[0] | 0x01, rand[1], rand[2], rand[3], rand[4], rand[5]]);
         }
 
-        Self::from(Node([0u8; 6]))
+        Node([0u8; 6])
     }
 }
 
@@ -60,44 +73,10 @@ impl From<[u8; 6]> for Node {
     }
 }
 
-/// Is a coordinated Universal Time (UTC).
-#[repr(u64)]
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
-pub enum Timestamp {
-    UTC(u64),
-}
-
-impl Timestamp {
-    /// Returns [`Timestamp`] value.
-    pub fn get(&self) -> u64 {
-        match self {
-            Timestamp::UTC(t) => *t,
-        }
-    }
-}
-
-impl std::default::Default for Timestamp {
-    #[allow(unreachable_code)]
-    fn default() -> Self {
-        #[cfg(feature = "utc")]
-        return Self::UTC(Utc::now().timestamp_nanos() as u64);
-
-        #[cfg(feature = "rand")]
-        {
-            use nanorand::Rng;
-
-            let buf = [0u8; 8];
-            nanorand::ChaCha::<6>::new().fill_bytes(buf);
-            return Self::UTC(u64::from_le_bytes(buf));
-        }
-
-        Self::UTC(u64::MAX)
-    }
-}
-
 /// The simplified version of [`UUID`] in terms of fields that are integral numbers of octets.
 pub struct Layout {
-    timestamp: Timestamp,
+    /// `time_low`, `time_mid` and `time_hi_and_version`, in [`UUID`] byte order.
+    time: [u8; 8],
     clock_seq: ClockSeq,
     node: Node,
 }
@@ -113,8 +92,9 @@ impl Layout {
     }
 
     /// New instance of [`UUID`].
+    #[allow(clippy::new_ret_no_self)]
     pub fn new(&mut self) -> UUID {
-        let ts = self.timestamp.get().to_le_bytes();
+        let ts = self.time;
         let cs = self.clock_seq.to_le_bytes();
         let n = self.node.0;
 
@@ -131,65 +111,86 @@ impl convert::From<UUID> for Layout {
     }
 }
 
+/// Returns the [`Layout`] of a version 1 [`UUID`] generated at `ts`, with a
+/// random clock sequence and the default [`Node`].
 impl convert::From<Timestamp> for Layout {
     fn from(ts: Timestamp) -> Self {
-        Self {
-            timestamp: ts,
-            clock_seq: ClockSeq::default(),
-            node: Node::default(),
-        }
+        let cs = ClockSeq::new().get().to_be_bytes();
+        rfc4122::v1::build(ts.to_gregorian(), cs, Node::default())
     }
 }
 
 impl convert::From<Node> for Layout {
     fn from(node: Node) -> Self {
         Self {
-            timestamp: Timestamp::UTC(0),
+            time: [0u8; 8],
             clock_seq: ClockSeq::default(),
-            node: node,
+            node,
         }
     }
 }
 
 /// A Universally Unique Identifier [`UUID`].
-#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+///
+/// Ordering compares the 16 bytes lexicographically, which is the same as
+/// comparing them as a big-endian 128-bit integer or comparing their lowercase
+/// hyphenated strings. Identifiers of versions 6 and 7 thus sort by creation
+/// time, while those of version 1 do not.
+///
+/// Hashing is the same as for `[u8; 16]`, so a map keyed by [`UUID`] can be
+/// queried with raw bytes through [`Borrow`](borrow::Borrow).
+#[derive(Default, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
 pub struct UUID(pub [u8; 16]);
 
 impl UUID {
     /// UUID namespace for domain name system (DNS).
-    pub const NAMESPACE_DNS: UUID = UUID([
-        0x6b, 0xa7, 0xb8, 0x10, 0x9d, 0xad, 0x11, 0xd1, 0x80, 0xb4, 0x00, 0xc0, 0x4f, 0xd4, 0x30,
-        0xc8,
-    ]);
+    pub const NAMESPACE_DNS: UUID = uuid!("6ba7b810-9dad-11d1-80b4-00c04fd430c8");
 
     /// UUID namespace for ISO object identifiers (OIDs).
-    pub const NAMESPACE_OID: UUID = UUID([
-        0x6b, 0xa7, 0xb8, 0x12, 0x9d, 0xad, 0x11, 0xd1, 0x80, 0xb4, 0x00, 0xc0, 0x4f, 0xd4, 0x30,
-        0xc8,
-    ]);
+    pub const NAMESPACE_OID: UUID = uuid!("6ba7b812-9dad-11d1-80b4-00c04fd430c8");
 
     /// UUID namespace for uniform resource locators (URLs).
-    pub const NAMESPACE_URL: UUID = UUID([
-        0x6b, 0xa7, 0xb8, 0x11, 0x9d, 0xad, 0x11, 0xd1, 0x80, 0xb4, 0x00, 0xc0, 0x4f, 0xd4, 0x30,
-        0xc8,
-    ]);
+    pub const NAMESPACE_URL: UUID = uuid!("6ba7b811-9dad-11d1-80b4-00c04fd430c8");
 
     /// UUID namespace for X.500 distinguished names (DNs).
-    pub const NAMESPACE_X500: UUID = UUID([
-        0x6b, 0xa7, 0xb8, 0x14, 0x9d, 0xad, 0x11, 0xd1, 0x80, 0xb4, 0x00, 0xc0, 0x4f, 0xd4, 0x30,
-        0xc8,
-    ]);
+    pub const NAMESPACE_X500: UUID = uuid!("6ba7b814-9dad-11d1-80b4-00c04fd430c8");
+
+    /// The Nil UUID with all 128 bits set to zero, see `rfc9562` section 5.9.
+    pub const NIL: UUID = UUID([0x00; 16]);
+
+    /// The Max UUID with all 128 bits set to one, see `rfc9562` section 5.10.
+    pub const MAX: UUID = UUID([0xff; 16]);
+
+    /// Returns `true` if this is the [`UUID::NIL`].
+    pub const fn is_nil(&self) -> bool {
+        self.as_u128() == u128::MIN
+    }
+
+    /// Returns `true` if this is the [`UUID::MAX`].
+    pub const fn is_max(&self) -> bool {
+        self.as_u128() == u128::MAX
+    }
 
     /// Returns the algorithm number of [`UUID`].
     ///
     /// See [`Version`] .
     pub fn get_version(&self) -> Result<Version, &str> {
+        if self.is_nil() {
+            return Ok(Version::NIL);
+        }
+        if self.is_max() {
+            return Ok(Version::MAX);
+        }
+
         match self.0[6] >> 4 {
             0x1 => Ok(Version::TIME),
             0x2 => Ok(Version::DCE),
             0x3 => Ok(Version::MD5),
             0x4 => Ok(Version::RAND),
             0x5 => Ok(Version::SHA1),
+            0x6 => Ok(Version::ORDERED),
+            0x7 => Ok(Version::UNIX),
+            0x8 => Ok(Version::CUSTOM),
             _ => Err("Invalid version"),
         }
     }
@@ -198,6 +199,13 @@ impl UUID {
     ///
     /// See [`Variant`]
     pub fn get_variant(&self) -> Result<Variant, &str> {
+        if self.is_nil() {
+            return Ok(Variant::NIL);
+        }
+        if self.is_max() {
+            return Ok(Variant::MAX);
+        }
+
         match (self.0[8] >> 0x5) & 0x7 {
             0x0..=0x3 => Ok(Variant::NCS),
             0x4 | 0x5 => Ok(Variant::RFC4122),
@@ -215,29 +223,88 @@ impl UUID {
         [node[10], node[11], node[12], node[13], node[14], node[15]].into()
     }
 
-    /// Parse [`UUID`] from string of hex digits.
-    pub fn parse(us: &str) -> Result<UUID, &str> {
-        let mut us = us.to_string();
-        let mut bytes = [0; 16];
-
-        if us.len() == 36 || us.len() == 32 {
-            if us.contains('-') {
-                us.retain(|c| !c.is_ascii_whitespace() && c != '-');
+    /// Returns the time at which a version 1, 6 or 7 [`UUID`] was generated,
+    /// or `None` for other versions.
+    pub fn get_timestamp(&self) -> Option<Timestamp> {
+        match self.get_version() {
+            Ok(Version::TIME) => Some(Timestamp::from_gregorian(rfc4122::v1::ticks(self))),
+            Ok(Version::ORDERED) => Some(Timestamp::from_gregorian(rfc4122::v6::ticks(self))),
+            Ok(Version::UNIX) => {
+                let b = self.0;
+                let millis = u64::from_be_bytes([0, 0, b[0], b[1], b[2], b[3], b[4], b[5]]);
+                Some(Timestamp::from_unix_millis(millis))
             }
-
-            for i in 0..15 {
-                let s = &us[i * 2..i * 2 + 2];
-                let byte = u8::from_str_radix(s, 16).map_err(|_| "Invalid UUID string")?;
-                bytes[i] = byte;
-            }
-        } else {
-            return Err("Invalid UUID string");
+            _ => None,
         }
+    }
 
-        Ok(UUID([
-            bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5], bytes[6], bytes[7],
-            bytes[8], bytes[9], bytes[10], bytes[11], bytes[12], bytes[13], bytes[14], bytes[15],
-        ]))
+    /// Creates [`UUID`] from a 128-bit integer, most significant byte first.
+    pub const fn from_u128(v: u128) -> UUID {
+        UUID(v.to_be_bytes())
+    }
+
+    /// Creates [`UUID`] from a 128-bit integer, least significant byte first.
+    pub const fn from_u128_le(v: u128) -> UUID {
+        UUID(v.to_le_bytes())
+    }
+
+    /// Returns the 128-bit integer read most significant byte first.
+    pub const fn as_u128(&self) -> u128 {
+        u128::from_be_bytes(self.0)
+    }
+
+    /// Returns the 128-bit integer read least significant byte first.
+    pub const fn to_u128_le(&self) -> u128 {
+        u128::from_le_bytes(self.0)
+    }
+
+    /// Creates [`UUID`] from its high and low 64 bits.
+    pub const fn from_u64_pair(high: u64, low: u64) -> UUID {
+        UUID::from_u128((high as u128) << 64 | low as u128)
+    }
+
+    /// Returns the high and low 64 bits.
+    pub const fn as_u64_pair(&self) -> (u64, u64) {
+        let v = self.as_u128();
+        ((v >> 64) as u64, v as u64)
+    }
+
+    /// Creates [`UUID`] from the fields defined in `rfc4122` section 4.1.2,
+    /// each stored most significant byte first.
+    ///
+    /// `clock_seq` holds both `clock_seq_hi_and_reserved` and `clock_seq_low`.
+    /// Version and variant bits are taken as given.
+    pub const fn from_fields(
+        time_low: u32,
+        time_mid: u16,
+        time_hi_version: u16,
+        clock_seq: u16,
+        node: Node,
+    ) -> UUID {
+        let tl = time_low.to_be_bytes();
+        let tm = time_mid.to_be_bytes();
+        let th = time_hi_version.to_be_bytes();
+        let cs = clock_seq.to_be_bytes();
+        let n = node.0;
+
+        UUID([
+            tl[0], tl[1], tl[2], tl[3], tm[0], tm[1], th[0], th[1], cs[0], cs[1], n[0], n[1], n[2],
+            n[3], n[4], n[5],
+        ])
+    }
+
+    /// Returns the `(time_low, time_mid, time_hi_version, clock_seq, node)` fields.
+    ///
+    /// See [`UUID::from_fields`].
+    pub const fn as_fields(&self) -> (u32, u16, u16, u16, Node) {
+        let b = self.0;
+        (
+            u32::from_be_bytes([b[0], b[1], b[2], b[3]]),
+            u16::from_be_bytes([b[4], b[5]]),
+            u16::from_be_bytes([b[6], b[7]]),
+            u16::from_be_bytes([b[8], b[9]]),
+            Node([b[10], b[11], b[12], b[13], b[14], b[15]]),
+        )
     }
 }
 
@@ -253,42 +320,74 @@ impl convert::From<UUID> for [u8; 16] {
     }
 }
 
+impl convert::TryFrom<&str> for UUID {
+    type Error = ParseError;
+
+    fn try_from(us: &str) -> Result<Self, Self::Error> {
+        UUID::parse(us)
+    }
+}
+
+impl convert::TryFrom<&[u8]> for UUID {
+    type Error = array::TryFromSliceError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        <[u8; 16]>::try_from(bytes).map(UUID)
+    }
+}
+
+impl convert::AsRef<[u8]> for UUID {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl borrow::Borrow<[u8; 16]> for UUID {
+    fn borrow(&self) -> &[u8; 16] {
+        &self.0
+    }
+}
+
+/// Reads the integer most significant byte first, see [`UUID::from_u128`].
 impl convert::From<u128> for UUID {
-    fn from(bytes: u128) -> Self {
-        UUID(bytes.to_le_bytes())
+    fn from(v: u128) -> Self {
+        UUID::from_u128(v)
+    }
+}
+
+/// See [`UUID::as_u128`].
+impl convert::From<UUID> for u128 {
+    fn from(uuid: UUID) -> Self {
+        uuid.as_u128()
     }
 }
 
 impl fmt::Display for UUID {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let b = self.0;
-        write!(
-            fmt,
-            "{:02x}{:02x}{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}{:02x}{:02x}{:02x}{:02x}",          
-            b[0],
-            b[1],
-            b[2],
-            b[3],
-            b[4],
-            b[5],
-            b[6],
-            b[7],
-            b[8],
-            b[9],
-            b[10],
-            b[11],
-            b[12],
-            b[13],
-            b[14],
-            b[15],
-        )
+        fmt::Display::fmt(&self.hyphenated(), fmt)
+    }
+}
+
+/// Formats as hyphenated, see [`adapter`] for the other formats.
+impl fmt::LowerHex for UUID {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerHex::fmt(&self.hyphenated(), fmt)
+    }
+}
+
+/// Formats as hyphenated, see [`adapter`] for the other formats.
+impl fmt::UpperHex for UUID {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::UpperHex::fmt(&self.hyphenated(), fmt)
     }
 }
 
 /// Represent the algorithm use for building the [`Layout`], located in
-/// the most significant 4 bits of [`Timestamp`].
+/// the most significant 4 bits of `time_hi_and_version`.
 #[derive(Debug, Eq, PartialEq, Clone, Copy)]
 pub enum Version {
+    /// The Nil UUID specified in `rfc9562` document.
+    NIL = 0,
     /// The time-based version specified in `rfc4122` document.
     TIME = 1,
     /// DCE-security version, with embedded POSIX UIDs.
@@ -299,6 +398,14 @@ pub enum Version {
     RAND,
     /// The name-based version specified in `rfc4122`document that uses SHA1 hashing.
     SHA1,
+    /// The time-based version specified in `rfc9562` document, reordered to sort by time.
+    ORDERED,
+    /// The time-ordered version specified in `rfc9562` document, based on Unix Epoch time.
+    UNIX,
+    /// The experimental or vendor-specific version specified in `rfc9562` document.
+    CUSTOM,
+    /// The Max UUID specified in `rfc9562` document.
+    MAX = 0xf,
 }
 
 /// Type field determines the layout of [`UUID`].
@@ -312,40 +419,69 @@ pub enum Variant {
     MS,
     /// Reserved for future definition.
     FUT,
+    /// The Nil UUID, whose bits would otherwise read as [`Variant::NCS`].
+    NIL,
+    /// The Max UUID, whose bits would otherwise read as [`Variant::FUT`].
+    MAX,
 }
 
 /// Ensure uniqueness.
+///
+/// The 14-bit clock sequence is changed whenever the time-based generators
+/// could otherwise repeat a [`UUID`], see `rfc4122` section 4.1.5.
+#[derive(Default)]
 pub struct ClockSeq {
     clk_seq_hi_res: u8,
     clk_seq_low: Mutex<u8>,
 }
 
 impl ClockSeq {
+    /// Returns a randomly initialized [`ClockSeq`].
     pub fn new() -> Self {
-        Self {
-            clk_seq_hi_res: u8::default(),
-            clk_seq_low: Mutex::default(),
-        }
+        Self::from(seed() as u16)
     }
 
-    pub fn to_le_bytes(&mut self) -> [u8; 2] {
-        u16::from_le_bytes([
-            self.clk_seq_hi_res << 7,
-            *self.clk_seq_low.get_mut().unwrap(),
+    /// Returns the 14-bit value of [`ClockSeq`].
+    pub fn get(&self) -> u16 {
+        u16::from_be_bytes([
+            self.clk_seq_hi_res & 0x3f,
+            *self.clk_seq_low.lock().unwrap(),
         ])
-        .to_le_bytes()
+    }
+
+    pub fn to_le_bytes(&mut self) -> [u8; 2] {
+        [self.clk_seq_hi_res, *self.clk_seq_low.get_mut().unwrap()]
     }
 }
 
-impl Default for ClockSeq {
-    fn default() -> Self {
+impl From<u16> for ClockSeq {
+    fn from(cs: u16) -> Self {
+        let cs = cs.to_be_bytes();
         Self {
-            clk_seq_hi_res: u8::default(),
-            clk_seq_low: Mutex::default(),
+            clk_seq_hi_res: (cs[0] & 0x3f) | 0x80,
+            clk_seq_low: Mutex::new(cs[1]),
         }
     }
 }
 
+/// Returns a random value suited to seed counters, not to build key material.
+#[allow(unreachable_code)]
+pub(crate) fn seed() -> u64 {
+    #[cfg(feature = "rand")]
+    return rfc4122::v4::get_random() as u64;
+
+    use std::hash::{BuildHasher, Hasher};
+
+    let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+    hasher.write_u128(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos(),
+    );
+    hasher.finish()
+}
+
 pub(crate) macro layout {
     ($b0:expr, $b1:expr, $b2:expr, $b3:expr,
                 $b4:expr, $b5:expr, $b6:expr, $b7:expr,
@@ -353,10 +489,8 @@ pub(crate) macro layout {
                 $b12:expr, $b13:expr, $b14:expr, $b15:expr
     ) => {
         Layout {
-            timestamp:  Timestamp::UTC($b0 as u64 | ($b1 as u64) << 8 | ($b2 as u64) << 16
-            | ($b3 as u64) << 24 | ($b4 as u64) << 32 | ($b5 as u64) << 40
-            | ($b6 as u64) << 48 | ($b7 as u64) << 56),
-            clock_seq: ClockSeq{clk_seq_hi_res: $b8 | Variant::RFC4122 as u8,
+            time: [$b0, $b1, $b2, $b3, $b4, $b5, $b6, $b7],
+            clock_seq: ClockSeq{clk_seq_hi_res: ($b8 & 0x3f) | 0x80,
                 clk_seq_low: Mutex::new($b9)},
             node: $crate::Node::from([$b10, $b11, $b12, $b13, $b14, $b15]),
         }
@@ -415,6 +549,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::clone_on_copy)]
     fn uuid_derive() {
         let default = UUID::default();
         let from = UUID::from([u8::MAX; 16]);
@@ -438,34 +573,180 @@ mod tests {
         let mut buffer = String::new();
 
         check!(buffer, "{}", uuid, 36, |c| c.is_lowercase()
-            || c.is_digit(10)
+            || c.is_ascii_digit()
             || c == '-');
         check!(buffer, "{}", uuid.to_string().to_lowercase(), 36, |c| c
             .is_lowercase()
-            || c.is_digit(10)
+            || c.is_ascii_digit()
             || c == '-');
         check!(buffer, "{}", uuid.to_string().to_uppercase(), 36, |c| c
             .is_uppercase()
-            || c.is_digit(10)
+            || c.is_ascii_digit()
             || c == '-');
     }
 
+    #[test]
+    fn uuid_ordering() {
+        let mut uuids: Vec<UUID> = UUIDS.iter().map(|us| UUID::parse(us).unwrap()).collect();
+        uuids.sort();
+
+        let mut strings = UUIDS.to_vec();
+        strings.sort();
+        assert_eq!(
+            uuids.iter().map(UUID::to_string).collect::<Vec<_>>(),
+            strings
+        );
+
+        let mut low = [0u8; 16];
+        low[15] = 1;
+        let mut high = [0xffu8; 16];
+        high[0] = 0;
+        assert!(UUID::default() < UUID::from(low));
+        assert!(UUID::from(high) < UUID::from([1; 16]));
+    }
+
+    #[test]
+    fn uuid_as_map_key() {
+        let uuid = UUID::parse(UUIDS[0]).unwrap();
+
+        let mut hash = std::collections::HashMap::new();
+        hash.insert(uuid, "hash");
+        assert_eq!(hash.get(&uuid.0), Some(&"hash"));
+
+        let mut btree = std::collections::BTreeMap::new();
+        btree.insert(uuid, "btree");
+        assert_eq!(btree.get(&uuid.0), Some(&"btree"));
+    }
+
+    #[test]
+    fn uuid_try_from() {
+        let uuid = UUID::try_from(UUIDS[1]).unwrap();
+        assert_eq!(uuid, UUIDS[1].parse().unwrap());
+        assert_eq!(UUID::try_from("-"), Err(ParseError::InvalidLength(1)));
+
+        assert_eq!(UUID::try_from(uuid.as_ref()).unwrap(), uuid);
+        assert!(UUID::try_from(&uuid.0[..15]).is_err());
+        assert!(UUID::try_from(&[0u8; 17][..]).is_err());
+    }
+
+    #[test]
+    fn uuid_from_integers() {
+        let uuid = UUID::parse("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap();
+        let v = 0x67e5504410b1426f9247bb680e5fe0c8u128;
+
+        assert_eq!(UUID::from(v), uuid);
+        assert_eq!(UUID::from_u128(v), uuid);
+        assert_eq!(u128::from(uuid), v);
+        assert_eq!(uuid.as_u128(), v);
+
+        assert_eq!(UUID::from(1u128).0[15], 1);
+        assert_eq!(UUID::from_u128_le(1).0[0], 1);
+        assert_eq!(UUID::from_u128_le(v).to_u128_le(), v);
+        assert_eq!(UUID::from_u128_le(v.swap_bytes()), uuid);
+
+        assert_eq!(uuid.as_u64_pair(), (0x67e5504410b1426f, 0x9247bb680e5fe0c8));
+        assert_eq!(
+            UUID::from_u64_pair(0x67e5504410b1426f, 0x9247bb680e5fe0c8),
+            uuid
+        );
+    }
+
+    #[test]
+    fn uuid_from_fields() {
+        let uuid = UUID::parse("c232ab00-9414-11ec-b3c8-9f6bdeced846").unwrap();
+        let node = Node([0x9f, 0x6b, 0xde, 0xce, 0xd8, 0x46]);
+
+        assert_eq!(uuid.as_fields(), (0xc232ab00, 0x9414, 0x11ec, 0xb3c8, node));
+        assert_eq!(
+            UUID::from_fields(0xc232ab00, 0x9414, 0x11ec, 0xb3c8, node),
+            uuid
+        );
+        assert_eq!(uuid.get_version(), Ok(Version::TIME));
+    }
+
+    #[test]
+    fn uuid_nil_and_max() {
+        assert_eq!(UUID::NIL, UUID::default());
+        assert_eq!(
+            UUID::NIL.to_string(),
+            "00000000-0000-0000-0000-000000000000"
+        );
+        assert_eq!(
+            UUID::MAX.to_string(),
+            "ffffffff-ffff-ffff-ffff-ffffffffffff"
+        );
+        assert_eq!(UUID::MAX, uuid!("FFFFFFFF-FFFF-FFFF-FFFF-FFFFFFFFFFFF"));
+
+        assert!(UUID::NIL.is_nil() && !UUID::NIL.is_max());
+        assert!(UUID::MAX.is_max() && !UUID::MAX.is_nil());
+        assert!(!UUID::parse(UUIDS[0]).unwrap().is_nil());
+
+        assert_eq!(UUID::NIL.get_version(), Ok(Version::NIL));
+        assert_eq!(UUID::NIL.get_variant(), Ok(Variant::NIL));
+        assert_eq!(UUID::MAX.get_version(), Ok(Version::MAX));
+        assert_eq!(UUID::MAX.get_variant(), Ok(Variant::MAX));
+
+        // Only the exact values are special.
+        assert_eq!(UUID::from(1u128).get_version(), Err("Invalid version"));
+        assert_eq!(UUID::from(1u128).get_variant(), Ok(Variant::NCS));
+    }
+
+    #[test]
+    fn uuid_get_timestamp() {
+        // Tuesday, February 22, 2022 2:22:22.000000 PM GMT-05:00, see `rfc9562` appendix A.
+        let expected = Timestamp::from_unix(1_645_557_742, 0);
+
+        for us in [
+            "c232ab00-9414-11ec-b3c8-9f6bdeced846",
+            "1ec9414c-232a-6b00-b3c8-9f6bdeced846",
+            "017f22e2-79b0-7cc3-98c4-dc0c0c07398f",
+        ] {
+            let ts = UUID::parse(us).unwrap().get_timestamp();
+            assert_eq!(ts, Some(expected), "{}", us);
+        }
+
+        assert_eq!(UUID::parse(UUIDS[3]).unwrap().get_timestamp(), None);
+        assert_eq!(UUID::NIL.get_timestamp(), None);
+        // The Gregorian epoch, 1582-10-15.
+        let ts = UUID::parse("00000000-0000-1000-8000-000000000000")
+            .unwrap()
+            .get_timestamp()
+            .unwrap();
+        assert_eq!(ts.secs(), -12_219_292_800);
+    }
+
+    #[test]
+    fn uuid_get_timestamp_of_generated() {
+        let before = Timestamp::now();
+        let ts = UUID::v6().new().get_timestamp().unwrap();
+        assert!(ts >= before - std::time::Duration::from_micros(1));
+        assert!(ts <= Timestamp::now() + std::time::Duration::from_secs(1));
+    }
+
     #[cfg(feature = "utc")]
+    #[test]
+    fn timestamp_into_utc() {
+        let uuid = UUID::parse("017f22e2-79b0-7cc3-98c4-dc0c0c07398f").unwrap();
+        let utc: chrono::DateTime<Utc> = uuid.get_timestamp().unwrap().try_into().unwrap();
+        assert_eq!(utc.to_rfc3339(), "2022-02-22T19:22:22+00:00");
+    }
+
     #[test]
     fn layout_from_timstamp() {
-        let mut utc = Timestamp::UTC(0x1234);
-        let mut layout = Layout::from(utc);
-        assert_eq!(layout.timestamp.get(), 0x1234);
-        assert_eq!(layout.timestamp.get().to_le_bytes()[0], 0x34);
-        assert_eq!(layout.timestamp.get().to_le_bytes()[1], 0x12);
-
-        utc = Timestamp::UTC(u64::MIN);
-        layout = Layout::from(utc);
-        assert_eq!(layout.timestamp.get(), u64::MIN);
-
-        utc = Timestamp::UTC(u64::MAX);
-        layout = Layout::from(utc);
-        assert_eq!(layout.timestamp.get(), u64::MAX)
+        let ts = Timestamp::from_unix(1_645_557_742, 123_456_700);
+        let uuid = Layout::from(ts).new();
+
+        assert_eq!(uuid.get_version(), Ok(Version::TIME));
+        assert_eq!(uuid.get_variant(), Ok(Variant::RFC4122));
+        assert_eq!(uuid.get_timestamp(), Some(ts));
+    }
+
+    #[cfg(all(feature = "rand", not(feature = "mac")))]
+    #[test]
+    fn node_default_is_random() {
+        let node = Node::default();
+        assert_eq!(node.0[0] & 0x01, 0x01);
+        assert_ne!(node, Node::default());
     }
 
     #[test]
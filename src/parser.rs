@@ -0,0 +1,436 @@
+use core::{fmt, str::FromStr};
+
+use crate::UUID;
+
+/// Positions of the group separators in the hyphenated format.
+const GROUPS: [usize; 4] = [8, 13, 18, 23];
+
+/// Textual forms a [`UUID`] can be parsed from.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Format {
+    /// 32 hex digits, like `67e5504410b1426f9247bb680e5fe0c8`.
+    Simple,
+    /// Hex digits in five groups separated by hyphens, like `67e55044-10b1-426f-9247-bb680e5fe0c8`.
+    Hyphenated,
+    /// The hyphenated form surrounded by braces, like `{67e55044-10b1-426f-9247-bb680e5fe0c8}`.
+    Braced,
+    /// The hyphenated form prefixed by `urn:uuid:`, like `urn:uuid:67e55044-10b1-426f-9247-bb680e5fe0c8`.
+    Urn,
+}
+
+impl Format {
+    /// Every supported [`Format`].
+    pub const ALL: [Format; 4] = [
+        Format::Simple,
+        Format::Hyphenated,
+        Format::Braced,
+        Format::Urn,
+    ];
+}
+
+/// Error returned when parsing a [`UUID`] from a string fails.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ParseError {
+    /// The input length, in bytes, matches none of the supported formats.
+    InvalidLength(usize),
+    /// A character that is not a hex digit was found at byte `index`.
+    InvalidChar { index: usize, found: char },
+    /// A group separator is missing, or found where a hex digit was expected, at byte `index`.
+    InvalidGroup { index: usize },
+    /// The input is in a [`Format`] that was not accepted.
+    InvalidFormat(Format),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::InvalidLength(len) => {
+                write!(
+                    fmt,
+                    "invalid length: expected 32, 36, 38 or 45 bytes, found {}",
+                    len
+                )
+            }
+            ParseError::InvalidChar { index, found } => {
+                write!(
+                    fmt,
+                    "invalid character: expected a hex digit, found {:?} at {}",
+                    found, index
+                )
+            }
+            ParseError::InvalidGroup { index } => {
+                write!(fmt, "invalid group separator at {}", index)
+            }
+            ParseError::InvalidFormat(format) => {
+                write!(fmt, "invalid format: {:?} is not accepted", format)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl UUID {
+    /// Parse [`UUID`] from string of hex digits, in any [`Format`].
+    ///
+    /// Hex digits and the `urn:uuid:` prefix are case-insensitive. Being a
+    /// `const fn`, it can also run at compile time, see [`uuid!`](crate::uuid).
+    pub const fn parse(us: &str) -> Result<UUID, ParseError> {
+        Self::parse_only(us, &Format::ALL)
+    }
+
+    /// Parse [`UUID`] from string of hex digits, in one of the given `formats`.
+    pub const fn parse_only(us: &str, formats: &[Format]) -> Result<UUID, ParseError> {
+        let input = us.as_bytes();
+        let format = match input.len() {
+            32 => Format::Simple,
+            36 => Format::Hyphenated,
+            38 => Format::Braced,
+            45 => Format::Urn,
+            len => return Err(ParseError::InvalidLength(len)),
+        };
+
+        let (start, end) = match format {
+            Format::Simple | Format::Hyphenated => (0, input.len()),
+            Format::Braced => {
+                if let Err(err) = expect(input, 0, b"{") {
+                    return Err(err);
+                }
+                (1, 37)
+            }
+            Format::Urn => {
+                if let Err(err) = expect(input, 0, URN_PREFIX) {
+                    return Err(err);
+                }
+                (URN_PREFIX.len(), 45)
+            }
+        };
+        if !accepts(formats, format) {
+            return Err(ParseError::InvalidFormat(format));
+        }
+
+        let hyphenated = !matches!(format, Format::Simple);
+        let mut bytes = [0u8; 16];
+        let mut digit = 0;
+        let mut index = start;
+
+        while index < end {
+            let c = input[index];
+            let separator = hyphenated && is_group(index - start);
+            if separator != (c == b'-') {
+                return Err(ParseError::InvalidGroup { index });
+            }
+
+            if !separator {
+                let nibble = match hex(c) {
+                    Some(nibble) => nibble,
+                    None => return Err(invalid_char(input, index)),
+                };
+                bytes[digit / 2] |= nibble << (4 * (1 - digit % 2));
+                digit += 1;
+            }
+            index += 1;
+        }
+
+        if let Format::Braced = format {
+            if let Err(err) = expect(input, 37, b"}") {
+                return Err(err);
+            }
+        }
+
+        Ok(UUID(bytes))
+    }
+}
+
+impl FromStr for UUID {
+    type Err = ParseError;
+
+    fn from_str(us: &str) -> Result<Self, Self::Err> {
+        UUID::parse(us)
+    }
+}
+
+/// Parses a [`UUID`] literal, failing compilation when it is malformed.
+///
+/// ```
+/// use unik::{uuid, UUID};
+///
+/// const ID: UUID = uuid!("67e55044-10b1-426f-9247-bb680e5fe0c8");
+/// static URN: UUID = uuid!("urn:uuid:67e55044-10b1-426f-9247-bb680e5fe0c8");
+///
+/// assert_eq!(ID, URN);
+/// ```
+///
+/// ```compile_fail
+/// let id = unik::uuid!("67e55044-10b1-426f-9247-bb680e5fe0cg");
+/// ```
+pub macro uuid($us:literal) {{
+    const UUID: $crate::UUID = match $crate::UUID::parse($us) {
+        Ok(uuid) => uuid,
+        Err($crate::ParseError::InvalidLength(_)) => panic!("invalid UUID literal: invalid length"),
+        Err($crate::ParseError::InvalidChar { .. }) => {
+            panic!("invalid UUID literal: invalid character")
+        }
+        Err($crate::ParseError::InvalidGroup { .. }) => {
+            panic!("invalid UUID literal: invalid group separator")
+        }
+        Err($crate::ParseError::InvalidFormat(_)) => panic!("invalid UUID literal: invalid format"),
+    };
+    UUID
+}}
+
+const URN_PREFIX: &[u8] = b"urn:uuid:";
+
+const fn accepts(formats: &[Format], format: Format) -> bool {
+    let mut i = 0;
+    while i < formats.len() {
+        if formats[i] as u8 == format as u8 {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+const fn is_group(index: usize) -> bool {
+    let mut i = 0;
+    while i < GROUPS.len() {
+        if GROUPS[i] == index {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+/// Checks that `input` contains `expected` at byte `index`, ignoring ASCII case.
+const fn expect(input: &[u8], index: usize, expected: &[u8]) -> Result<(), ParseError> {
+    let mut i = 0;
+    while i < expected.len() {
+        if !input[index + i].eq_ignore_ascii_case(&expected[i]) {
+            return Err(invalid_char(input, index + i));
+        }
+        i += 1;
+    }
+    Ok(())
+}
+
+/// Decodes the character starting at byte `index` of the UTF-8 `input`.
+///
+/// Every expected byte is ASCII, so the first unexpected one starts a character.
+const fn invalid_char(input: &[u8], index: usize) -> ParseError {
+    let lead = input[index];
+    let (len, mut code) = match lead {
+        0x00..=0x7f => (1, lead as u32),
+        0xc0..=0xdf => (2, (lead & 0x1f) as u32),
+        0xe0..=0xef => (3, (lead & 0x0f) as u32),
+        _ => (4, (lead & 0x07) as u32),
+    };
+
+    let mut i = 1;
+    while i < len {
+        code = (code << 6) | (input[index + i] & 0x3f) as u32;
+        i += 1;
+    }
+
+    let found = match char::from_u32(code) {
+        Some(c) => c,
+        None => char::REPLACEMENT_CHARACTER,
+    };
+    ParseError::InvalidChar { index, found }
+}
+
+const fn hex(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXPECTED: UUID = UUID([
+        0x67, 0xe5, 0x50, 0x44, 0x10, 0xb1, 0x42, 0x6f, 0x92, 0x47, 0xbb, 0x68, 0x0e, 0x5f, 0xe0,
+        0xc8,
+    ]);
+
+    #[test]
+    fn parse_all_bytes() {
+        assert_eq!(
+            UUID::parse("67e55044-10b1-426f-9247-bb680e5fe0c8"),
+            Ok(EXPECTED)
+        );
+        assert_eq!(
+            UUID::parse("67E55044-10B1-426F-9247-BB680E5FE0C8"),
+            Ok(EXPECTED)
+        );
+        assert_eq!(
+            UUID::parse("67e5504410b1426f9247bb680e5fe0c8"),
+            Ok(EXPECTED)
+        );
+        assert_eq!(
+            UUID::parse("ffffffff-ffff-ffff-ffff-ffffffffffff"),
+            Ok(UUID([0xff; 16]))
+        );
+    }
+
+    #[test]
+    fn parse_invalid_length() {
+        assert_eq!(UUID::parse(""), Err(ParseError::InvalidLength(0)));
+        assert_eq!(
+            UUID::parse("67e55044-10b1-426f-9247-bb680e5fe0c"),
+            Err(ParseError::InvalidLength(35))
+        );
+        assert_eq!(
+            UUID::parse("67e5504410b1426f9247bb680e5fe0c8a"),
+            Err(ParseError::InvalidLength(33))
+        );
+    }
+
+    #[test]
+    fn parse_invalid_char() {
+        assert_eq!(
+            UUID::parse("67e55044-10b1-426f-9247-bb680e5fe0cg"),
+            Err(ParseError::InvalidChar {
+                index: 35,
+                found: 'g'
+            })
+        );
+        assert_eq!(
+            UUID::parse("67e5504410b1426f9247bb680e5fe0é"),
+            Err(ParseError::InvalidChar {
+                index: 30,
+                found: 'é'
+            })
+        );
+        assert_eq!(
+            UUID::parse("67e55044 10b1 426f 9247 bb680e5fe0c8"),
+            Err(ParseError::InvalidGroup { index: 8 })
+        );
+    }
+
+    #[test]
+    fn parse_invalid_group() {
+        assert_eq!(
+            UUID::parse("67e5504-410b1-426f-9247-bb680e5fe0c8"),
+            Err(ParseError::InvalidGroup { index: 7 })
+        );
+        assert_eq!(
+            UUID::parse("67e55044-10b1-426f-9247bb680e5fe0c8-"),
+            Err(ParseError::InvalidGroup { index: 23 })
+        );
+        assert_eq!(
+            UUID::parse("67e55044-10b1426f9247bb680e5fe0c"),
+            Err(ParseError::InvalidGroup { index: 8 })
+        );
+    }
+
+    #[test]
+    fn parse_error_display() {
+        let err: Box<dyn std::error::Error> = Box::new(ParseError::InvalidGroup { index: 7 });
+        assert_eq!(err.to_string(), "invalid group separator at 7");
+        assert_eq!(
+            ParseError::InvalidLength(3).to_string(),
+            "invalid length: expected 32, 36, 38 or 45 bytes, found 3"
+        );
+    }
+
+    #[test]
+    fn parse_other_formats() {
+        let inputs = [
+            "{67e55044-10b1-426f-9247-bb680e5fe0c8}",
+            "{67E55044-10B1-426F-9247-BB680E5FE0C8}",
+            "urn:uuid:67e55044-10b1-426f-9247-bb680e5fe0c8",
+            "URN:UUID:67E55044-10B1-426F-9247-BB680E5FE0C8",
+        ];
+
+        for input in inputs {
+            assert_eq!(UUID::parse(input), Ok(EXPECTED));
+            assert_eq!(input.parse::<UUID>(), Ok(EXPECTED));
+        }
+    }
+
+    #[test]
+    fn parse_invalid_prefix() {
+        assert_eq!(
+            UUID::parse("[67e55044-10b1-426f-9247-bb680e5fe0c8}"),
+            Err(ParseError::InvalidChar {
+                index: 0,
+                found: '['
+            })
+        );
+        assert_eq!(
+            UUID::parse("{67e55044-10b1-426f-9247-bb680e5fe0c8]"),
+            Err(ParseError::InvalidChar {
+                index: 37,
+                found: ']'
+            })
+        );
+        assert_eq!(
+            UUID::parse("urn:guid:67e55044-10b1-426f-9247-bb680e5fe0c8"),
+            Err(ParseError::InvalidChar {
+                index: 4,
+                found: 'g'
+            })
+        );
+        assert_eq!(
+            UUID::parse("{67e55044-10b1-426f-9247-bb680e5fe0cx}"),
+            Err(ParseError::InvalidChar {
+                index: 36,
+                found: 'x'
+            })
+        );
+        assert_eq!(
+            UUID::parse("urn:uuid:67e55044-10b1-426f-9247bbb680e5fe0c8"),
+            Err(ParseError::InvalidGroup { index: 32 })
+        );
+    }
+
+    #[test]
+    fn parse_only_accepted_formats() {
+        let hyphenated = "67e55044-10b1-426f-9247-bb680e5fe0c8";
+        let braced = "{67e55044-10b1-426f-9247-bb680e5fe0c8}";
+
+        assert_eq!(
+            UUID::parse_only(hyphenated, &[Format::Hyphenated]),
+            Ok(EXPECTED)
+        );
+        assert_eq!(
+            UUID::parse_only(braced, &[Format::Hyphenated, Format::Urn]),
+            Err(ParseError::InvalidFormat(Format::Braced))
+        );
+        assert_eq!(
+            UUID::parse_only("67e5504410b1426f9247bb680e5fe0c8", &[]),
+            Err(ParseError::InvalidFormat(Format::Simple))
+        );
+    }
+
+    #[test]
+    fn parse_at_compile_time() {
+        const ID: UUID = uuid!("67e55044-10b1-426f-9247-bb680e5fe0c8");
+        static BRACED: UUID = uuid!("{67E55044-10B1-426F-9247-BB680E5FE0C8}");
+
+        assert_eq!(ID, EXPECTED);
+        assert_eq!(BRACED, EXPECTED);
+        assert_eq!(uuid!("67e5504410b1426f9247bb680e5fe0c8"), EXPECTED);
+        assert_eq!(
+            UUID::NAMESPACE_DNS,
+            uuid!("6ba7b810-9dad-11d1-80b4-00c04fd430c8")
+        );
+    }
+
+    #[test]
+    fn parse_multibyte_char() {
+        assert_eq!(
+            UUID::parse("{67e55044-10b1-426f-9247-bb680e5fe😀"),
+            Err(ParseError::InvalidChar {
+                index: 34,
+                found: '😀'
+            })
+        );
+    }
+}
@@ -0,0 +1,106 @@
+//! Iterators generating [`UUID`]s in batches, see [`UUID::v4_iter`] and [`UUID::v7_iter`].
+
+use core::iter::FusedIterator;
+
+use crate::UUID;
+
+/// Number of identifiers generated at once.
+const BATCH: usize = 64;
+
+/// An endless iterator filling a buffer of [`UUID`]s at a time.
+///
+/// Each batch only borrows the generator once, while identifiers are still
+/// generated on demand, so that e.g. `take(n)` wastes at most one batch.
+#[derive(Debug)]
+pub struct Batched {
+    fill: fn(&mut [UUID]),
+    buf: [UUID; BATCH],
+    next: usize,
+}
+
+impl Batched {
+    fn new(fill: fn(&mut [UUID])) -> Self {
+        Self {
+            fill,
+            buf: [UUID::NIL; BATCH],
+            next: BATCH,
+        }
+    }
+}
+
+/// Returns a new iterator from the same generator, starting with an empty
+/// buffer so that it never repeats the identifiers left in `self`.
+impl Clone for Batched {
+    fn clone(&self) -> Self {
+        Self::new(self.fill)
+    }
+}
+
+impl UUID {
+    /// Returns an endless iterator of random identifiers, see [`UUID::fill_v4`].
+    pub fn v4_iter() -> Batched {
+        Batched::new(UUID::fill_v4)
+    }
+
+    /// Returns an endless iterator of time-ordered identifiers, see [`UUID::fill_v7`].
+    ///
+    /// The identifiers are strictly increasing, also across batches.
+    pub fn v7_iter() -> Batched {
+        Batched::new(UUID::fill_v7)
+    }
+}
+
+impl Iterator for Batched {
+    type Item = UUID;
+
+    fn next(&mut self) -> Option<UUID> {
+        if self.next == BATCH {
+            (self.fill)(&mut self.buf);
+            self.next = 0;
+        }
+
+        self.next += 1;
+        Some(self.buf[self.next - 1])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+impl FusedIterator for Batched {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Version;
+
+    #[test]
+    fn iterate_across_batches() {
+        let uuids: Vec<_> = UUID::v4_iter().take(BATCH * 3 + 1).collect();
+        let unique: std::collections::HashSet<_> = uuids.iter().collect();
+        assert_eq!(unique.len(), uuids.len());
+        assert!(uuids
+            .iter()
+            .all(|uuid| uuid.get_version() == Ok(Version::RAND)));
+
+        let uuids: Vec<_> = UUID::v7_iter().take(BATCH * 3 + 1).collect();
+        for pair in uuids.windows(2) {
+            assert!(pair[0] < pair[1], "{} >= {}", pair[0], pair[1]);
+        }
+        assert!(uuids
+            .iter()
+            .all(|uuid| uuid.get_version() == Ok(Version::UNIX)));
+    }
+
+    #[test]
+    fn clones_do_not_repeat() {
+        let mut iter = UUID::v4_iter();
+        iter.next();
+        let clone = iter.clone();
+
+        let uuids: Vec<_> = iter.take(BATCH).chain(clone.take(BATCH)).collect();
+        let unique: std::collections::HashSet<_> = uuids.iter().collect();
+        assert_eq!(unique.len(), uuids.len());
+    }
+}
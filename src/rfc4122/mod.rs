@@ -1,3 +1,8 @@
+#[cfg(feature = "rand")]
+pub mod batch;
+
+pub mod name;
+
 pub mod v1;
 
 #[cfg(feature = "v2")]
@@ -10,3 +15,9 @@ pub mod v4;
 
 #[cfg(feature = "v5")]
 pub mod v5;
+
+pub mod v6;
+
+pub mod v7;
+
+pub mod v8;
@@ -0,0 +1,153 @@
+#![cfg(any(feature = "v3", feature = "v5"))]
+
+use std::io;
+
+use crate::{Layout, UUID};
+
+/// Incrementally hashes a name, to build name-based `UUID`s from large or
+/// streamed input without first collecting it in memory.
+///
+/// The hashing algorithm is picked by the constructor, [`NameBasedBuilder::new_v3`]
+/// or [`NameBasedBuilder::new_v5`], so the input is hashed only once.
+#[derive(Clone)]
+pub struct NameBasedBuilder<H>(H);
+
+#[cfg(feature = "v3")]
+impl NameBasedBuilder<md5::Context> {
+    /// Starts hashing a name within the `ns` namespace using MD5 algorithm.
+    ///
+    /// See [`UUID::v3`].
+    pub fn new_v3(ns: UUID) -> Self {
+        let mut builder = Self(md5::Context::new());
+        builder.update(ns.0);
+        builder
+    }
+
+    /// Appends `data` to the name.
+    pub fn update<T: AsRef<[u8]>>(&mut self, data: T) -> &mut Self {
+        self.0.consume(data.as_ref());
+        self
+    }
+
+    /// Returns the version 3 [`Layout`] of the name hashed so far.
+    pub fn finish_v3(&self) -> Layout {
+        crate::rfc4122::v3::build(self.0.clone().compute().0)
+    }
+}
+
+#[cfg(feature = "v5")]
+impl NameBasedBuilder<sha1::Sha1> {
+    /// Starts hashing a name within the `ns` namespace using SHA1 algorithm.
+    ///
+    /// See [`UUID::v5`].
+    pub fn new_v5(ns: UUID) -> Self {
+        let mut builder = Self(sha1::Sha1::new());
+        builder.update(ns.0);
+        builder
+    }
+
+    /// Appends `data` to the name.
+    pub fn update<T: AsRef<[u8]>>(&mut self, data: T) -> &mut Self {
+        self.0.update(data.as_ref());
+        self
+    }
+
+    /// Returns the version 5 [`Layout`] of the name hashed so far.
+    pub fn finish_v5(&self) -> Layout {
+        let hash = self.0.digest().bytes();
+        crate::rfc4122::v5::build(hash[..16].try_into().unwrap())
+    }
+}
+
+#[cfg(feature = "v3")]
+impl io::Write for NameBasedBuilder<md5::Context> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "v5")]
+impl io::Write for NameBasedBuilder<sha1::Sha1> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NAME: &[u8] = b"http://python.org/";
+
+    #[cfg(feature = "v3")]
+    #[test]
+    fn builder_matches_v3() {
+        let mut builder = NameBasedBuilder::new_v3(UUID::NAMESPACE_URL);
+        for chunk in NAME.chunks(5) {
+            builder.update(chunk);
+        }
+
+        let expected = UUID::v3_bytes(NAME, UUID::NAMESPACE_URL).new();
+        assert_eq!(builder.finish_v3().new(), expected);
+        assert_eq!(expected.to_string(), "9fe8e8c4-aaa8-32a9-a55c-4535a88b748d");
+    }
+
+    #[cfg(feature = "v5")]
+    #[test]
+    fn builder_matches_v5() {
+        let uuid = NameBasedBuilder::new_v5(UUID::NAMESPACE_URL)
+            .update("http://")
+            .update(b"python.org/")
+            .finish_v5()
+            .new();
+
+        assert_eq!(uuid, UUID::v5_bytes(NAME, UUID::NAMESPACE_URL).new());
+        assert_eq!(uuid.to_string(), "4c565f0d-3f5a-5890-b41b-20cf47701c5e");
+    }
+
+    #[cfg(feature = "v5")]
+    #[test]
+    fn builder_from_reader() {
+        let mut builder = NameBasedBuilder::new_v5(UUID::NAMESPACE_URL);
+        io::copy(&mut io::Cursor::new(NAME), &mut builder).unwrap();
+
+        // Finishing does not consume the builder.
+        assert_eq!(builder.finish_v5().new(), builder.finish_v5().new());
+        assert_eq!(
+            builder.finish_v5().new(),
+            UUID::v5_bytes(NAME, UUID::NAMESPACE_URL).new()
+        );
+    }
+
+    #[test]
+    fn builder_accepts_binary_keys() {
+        let key = [0x00, 0xff, 0x80, 0x7f];
+
+        #[cfg(feature = "v3")]
+        assert_eq!(
+            NameBasedBuilder::new_v3(UUID::NAMESPACE_OID)
+                .update(key)
+                .finish_v3()
+                .new(),
+            UUID::v3_bytes(&key, UUID::NAMESPACE_OID).new()
+        );
+        #[cfg(feature = "v5")]
+        assert_eq!(
+            NameBasedBuilder::new_v5(UUID::NAMESPACE_OID)
+                .update(key)
+                .finish_v5()
+                .new(),
+            UUID::v5_bytes(&key, UUID::NAMESPACE_OID).new()
+        );
+    }
+}
@@ -1,49 +1,60 @@
-#![cfg(any(feature = "utc", feature = "rand"))]
-
-use crate::{layout, ClockSeq, Layout, Node, Timestamp, Version, UUID};
+use crate::{layout, Generator, Layout, Node, Version, UUID};
 
 impl UUID {
-    /// Returns the [`Layout`] generated from [`Node`] and [`Timestamp`].
+    /// Returns the [`Layout`] generated from [`Node`] and a Gregorian timestamp.
+    ///
+    /// Concurrent callers within one process never get the same [`UUID`],
+    /// see [`state`](crate::state) to extend this across processes and restarts.
+    /// The time is read from the [`clock`](crate::clock).
+    ///
+    /// Delegates to the global [`Generator`].
     pub fn v1() -> Layout {
-        let ts = [0u8; 8];
-        #[cfg(any(feature = "utc", feature = "rand"))]
-        {
-            let ts = Timestamp::default().get().to_le_bytes();
-        }
+        Generator::with_current(Generator::v1)
+    }
+}
 
-        let cshr = ClockSeq::new().to_le_bytes();
+/// Returns the [`Layout`] of a version 1 `UUID` from a 60-bit Gregorian timestamp,
+/// see `rfc4122` section 4.2.2.
+pub(crate) fn build(ticks: u64, cs: [u8; 2], node: Node) -> Layout {
+    let tl = (ticks as u32).to_be_bytes();
+    let tm = ((ticks >> 32) as u16).to_be_bytes();
+    let th = ((ticks >> 48) as u16).to_be_bytes();
+    let n = node.0;
 
-        let n = [0u8; 6];
-        #[cfg(feature = "rand")]
-        {
-            let n = Node::default().0;
-        }
+    layout!(
+        tl[0],
+        tl[1],
+        tl[2],
+        tl[3],
+        tm[0],
+        tm[1],
+        ((Version::TIME as u8) << 0x4) | (th[0] & 0xf),
+        th[1],
+        cs[0],
+        cs[1],
+        n[0],
+        n[1],
+        n[2],
+        n[3],
+        n[4],
+        n[5]
+    )
+}
 
-        layout!(
-            ts[0],
-            ts[1],
-            ts[2],
-            ts[3],
-            ts[4],
-            ts[5],
-            ((Version::TIME as u8) << 0x4) | (ts[6] & 0xf),
-            ts[7],
-            cshr[0],
-            cshr[1],
-            n[0],
-            n[1],
-            n[2],
-            n[3],
-            n[4],
-            n[5]
-        )
-    }
+/// Returns the 60-bit timestamp of a version 1 `UUID`.
+pub(crate) fn ticks(uuid: &UUID) -> u64 {
+    let b = uuid.0;
+    (b[6] as u64 & 0xf) << 56
+        | (b[7] as u64) << 48
+        | (b[4] as u64) << 40
+        | (b[5] as u64) << 32
+        | u32::from_be_bytes([b[0], b[1], b[2], b[3]]) as u64
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::Variant;
+    use crate::{ClockSeq, Timestamp, Variant};
 
     #[test]
     fn uuid_default() {
@@ -60,4 +71,51 @@ mod tests {
         assert_eq!(layout.get_version(), Ok(Version::TIME));
         assert_eq!(layout.get_variant(), Ok(Variant::RFC4122));
     }
+
+    #[test]
+    fn uuid_rfc9562_example() {
+        // Tuesday, February 22, 2022 2:22:22.000000 PM GMT-05:00, see `rfc9562` appendix A.1.
+        let node = Node([0x9f, 0x6b, 0xde, 0xce, 0xd8, 0x46]);
+        let uuid = build(0x1ec9414c232ab00, [0x33, 0xc8], node).new();
+
+        assert_eq!(uuid.to_string(), "c232ab00-9414-11ec-b3c8-9f6bdeced846");
+        assert_eq!(ticks(&uuid), 0x1ec9414c232ab00);
+        assert_eq!(uuid.get_node(), node);
+    }
+
+    #[test]
+    fn uuid_embeds_current_time() {
+        let before = Timestamp::now().to_gregorian();
+        let uuid = UUID::v1().new();
+        let after = Timestamp::now().to_gregorian();
+
+        assert!(ticks(&uuid) >= before);
+        assert!(ticks(&uuid) <= after + 1_000);
+        assert_eq!(uuid.get_node(), Generator::global().node());
+    }
+
+    #[test]
+    fn uuid_unique_across_threads() {
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                std::thread::spawn(|| (0..1_000).map(|_| UUID::v1().new().0).collect::<Vec<_>>())
+            })
+            .collect();
+
+        let mut uuids = std::collections::HashSet::new();
+        for handle in handles {
+            for uuid in handle.join().unwrap() {
+                assert!(uuids.insert(uuid));
+            }
+        }
+    }
+
+    #[test]
+    fn clock_seq_is_14_bits() {
+        for _ in 0..100 {
+            assert!(ClockSeq::new().get() <= 0x3fff);
+        }
+        assert_eq!(ClockSeq::from(0xffff).get(), 0x3fff);
+        assert_eq!(ClockSeq::from(0x1234).to_le_bytes(), [0x92, 0x34]);
+    }
 }
@@ -1,5 +1,3 @@
-#![cfg(any(feature = "utc", feature = "rand"))]
-
 use crate::{layout, Layout, Version, UUID};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -11,7 +9,7 @@ pub enum Domain {
 
 impl UUID {
     pub fn get_domain(&self) -> Result<Domain, &str> {
-        match self.0[7] {
+        match self.0[9] {
             0 => Ok(Domain::PERSON),
             1 => Ok(Domain::GROUP),
             2 => Ok(Domain::ORG),
@@ -24,12 +22,12 @@ impl UUID {
     pub fn v2(domain: Domain) -> Layout {
         let i: [u8; 4];
 
-        #[cfg(all(windows))]
+        #[cfg(windows)]
         {
             i = unsafe { libc::getpid() as u32 }.to_le_bytes();
         }
 
-        #[cfg(all(unix))]
+        #[cfg(unix)]
         {
             i = {
                 if domain == Domain::PERSON {
@@ -45,10 +43,6 @@ impl UUID {
         let mut bytes = UUID::v1().new().0;
 
         bytes[0..4].copy_from_slice(&i);
-        bytes[6] = (Version::DCE as u8) << 4;
-        bytes[9] = domain as u8;
-
-        // Layout::from_raw_bytes(uuid)
 
         layout!(
             bytes[0],
@@ -59,14 +53,14 @@ impl UUID {
             bytes[5],
             (Version::DCE as u8) << 0x4 | bytes[6] & 0xf,
             bytes[7],
-            bytes[0],
-            bytes[1],
-            bytes[0],
-            bytes[1],
-            bytes[2],
-            bytes[3],
-            bytes[4],
-            bytes[5]
+            bytes[8],
+            domain as u8,
+            bytes[10],
+            bytes[11],
+            bytes[12],
+            bytes[13],
+            bytes[14],
+            bytes[15]
         )
     }
 }
@@ -1,42 +1,51 @@
-use sha1::Sha1;
-
 use crate::{layout, Layout, Version, UUID};
 
 impl UUID {
     /// Creates `UUID` by hashing a namespace identifier and name using MD5 algorithm.
-    pub fn v3<'a>(data: &str, ns: UUID) -> Layout {
-        let hash: [u8; 16] = Sha1::from(format!("{}", ns) + data).digest().bytes()[..16]
-            .try_into()
-            .unwrap();
-
-        layout!(
-            hash[0],
-            hash[1],
-            hash[2],
-            hash[3],
-            hash[4],
-            hash[5],
-            ((Version::MD5 as u8) << 0x4) | (hash[6] & 0xf),
-            hash[7],
-            hash[8],
-            hash[9],
-            hash[10],
-            hash[11],
-            hash[12],
-            hash[13],
-            hash[14],
-            hash[15]
-        )
+    pub fn v3(data: &str, ns: UUID) -> Layout {
+        Self::v3_bytes(data.as_bytes(), ns)
+    }
+
+    /// Same as [`UUID::v3`], with a name made of arbitrary bytes.
+    ///
+    /// See [`NameBasedBuilder::new_v3`](crate::rfc4122::name::NameBasedBuilder::new_v3) to hash a name piece by piece.
+    pub fn v3_bytes(data: &[u8], ns: UUID) -> Layout {
+        let mut ctx = md5::Context::new();
+        ctx.consume(ns.0);
+        ctx.consume(data);
+        build(ctx.compute().0)
     }
 }
 
+/// Returns the [`Layout`] of a version 3 `UUID` from the MD5 hash of its name.
+pub(crate) fn build(hash: [u8; 16]) -> Layout {
+    layout!(
+        hash[0],
+        hash[1],
+        hash[2],
+        hash[3],
+        hash[4],
+        hash[5],
+        ((Version::MD5 as u8) << 0x4) | (hash[6] & 0xf),
+        hash[7],
+        hash[8],
+        hash[9],
+        hash[10],
+        hash[11],
+        hash[12],
+        hash[13],
+        hash[14],
+        hash[15]
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::Variant;
 
     #[test]
-    fn uuid_using_hash_sha1() {
+    fn uuid_using_hash_md5() {
         let nss = [
             UUID::NAMESPACE_DNS,
             UUID::NAMESPACE_OID,
@@ -50,4 +59,39 @@ mod tests {
             assert_eq!(uuid.get_variant(), Ok(Variant::RFC4122));
         }
     }
+
+    #[test]
+    fn uuid_conformance() {
+        let vectors = [
+            (
+                UUID::NAMESPACE_DNS,
+                "python.org",
+                "6fa459ea-ee8a-3ca4-894e-db77e160355e",
+            ),
+            (
+                UUID::NAMESPACE_DNS,
+                "www.example.com",
+                "5df41881-3aed-3515-88a7-2f4a814cf09e",
+            ),
+            (
+                UUID::NAMESPACE_URL,
+                "http://python.org/",
+                "9fe8e8c4-aaa8-32a9-a55c-4535a88b748d",
+            ),
+            (
+                UUID::NAMESPACE_OID,
+                "1.3.6.1",
+                "dd1a1cef-13d5-368a-ad82-eca71acd4cd1",
+            ),
+            (
+                UUID::NAMESPACE_X500,
+                "c=ca",
+                "658d3002-db6b-3040-a1d1-8ddd7d189a4d",
+            ),
+        ];
+
+        for (ns, name, expected) in vectors {
+            assert_eq!(UUID::v3(name, ns).new().to_string(), expected);
+        }
+    }
 }
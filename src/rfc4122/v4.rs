@@ -1,36 +1,231 @@
 #![cfg(feature = "rand")]
 
-use crate::{layout, Layout, Version, UUID};
+use std::{cell::RefCell, process};
 
-use nanorand::{Rng, WyRand};
+use crate::{layout, Generator, Layout, Version, UUID};
+
+use rand_chacha::ChaCha12Rng;
+use rand_core::{CryptoRng, OsRng, RngCore, SeedableRng};
+
+/// Bytes generated by a [`ThreadRng`] before it is reseeded.
+const RESEED_THRESHOLD: usize = 64 * 1024;
+
+thread_local! {
+    static RNG: RefCell<Option<Reseeding<ChaCha12Rng>>> = const { RefCell::new(None) };
+}
+
+#[cfg(feature = "fast-rng")]
+thread_local! {
+    static FAST_RNG: RefCell<Option<Reseeding<nanorand::WyRand>>> = const { RefCell::new(None) };
+}
 
 impl UUID {
     /// Creates a random `UUID`.
+    ///
+    /// Random bits are drawn from the [`ThreadRng`], see [`UUID::v4_fast`] for a
+    /// faster generator that is not cryptographically secure.
+    ///
+    /// Delegates to the global [`Generator`].
     pub fn v4() -> Layout {
-        let rand = get_random().to_le_bytes();
-        layout!(
-            rand[0],
-            rand[1],
-            rand[2],
-            rand[3],
-            rand[4],
-            rand[5],
-            ((Version::RAND as u8) << 0x4) | (rand[6] & 0xf),
-            rand[7],
-            rand[8],
-            rand[9],
-            rand[10],
-            rand[11],
-            rand[12],
-            rand[13],
-            rand[14],
-            rand[15]
-        )
+        Generator::with_current(Generator::v4)
+    }
+
+    /// Creates a random `UUID` from the bits generated by the [`FastRng`].
+    ///
+    /// Only use it for identifiers that need not be hard to guess, see [`FastRng`].
+    #[cfg(feature = "fast-rng")]
+    pub fn v4_fast() -> Layout {
+        let mut rand = [0u8; 16];
+        FastRng.fill_bytes(&mut rand);
+        build(rand)
+    }
+
+    /// Creates a random `UUID` from the bits generated by `rng`.
+    pub fn v4_with<R: RngCore + CryptoRng + ?Sized>(rng: &mut R) -> Layout {
+        let mut rand = [0u8; 16];
+        rng.fill_bytes(&mut rand);
+        build(rand)
+    }
+
+    /// Overwrites every [`UUID`] of `uuids` with a random one, like [`UUID::v4`]
+    /// but borrowing the generator only once.
+    pub fn fill_v4(uuids: &mut [UUID]) {
+        Generator::with_current(|generator| generator.fill_v4(uuids))
+    }
+}
+
+/// Returns the [`Layout`] of a version 4 `UUID`, see `rfc4122` section 4.4.
+pub(crate) fn build(rand: [u8; 16]) -> Layout {
+    layout!(
+        rand[0],
+        rand[1],
+        rand[2],
+        rand[3],
+        rand[4],
+        rand[5],
+        ((Version::RAND as u8) << 0x4) | (rand[6] & 0xf),
+        rand[7],
+        rand[8],
+        rand[9],
+        rand[10],
+        rand[11],
+        rand[12],
+        rand[13],
+        rand[14],
+        rand[15]
+    )
+}
+
+/// A ChaCha generator local to the current thread and seeded by the operating system.
+///
+/// Entropy is only read once every 64 KiB of output, and whenever the process id
+/// changes, so that a child process never repeats the sequence of its parent after `fork()`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ThreadRng;
+
+impl RngCore for ThreadRng {
+    fn next_u32(&mut self) -> u32 {
+        rand_core::impls::next_u32_via_fill(self)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        rand_core::impls::next_u64_via_fill(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        RNG.with(|rng| {
+            rng.borrow_mut()
+                .get_or_insert_with(Reseeding::new)
+                .fill_bytes(dest)
+        })
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl CryptoRng for ThreadRng {}
+
+/// A WyRand generator local to the current thread, faster than the [`ThreadRng`]
+/// but not cryptographically secure.
+///
+/// Its state is only 64 bits wide, so a version 4 `UUID` carries at most 64 bits
+/// of entropy instead of 122, and can be predicted from the previous ones of the
+/// same thread. It is seeded and reseeded by the operating system like the [`ThreadRng`].
+///
+/// It is never used unless picked explicitly, with [`UUID::v4_fast`] or
+/// [`GeneratorBuilder::fast_rng`](crate::GeneratorBuilder::fast_rng).
+#[cfg(feature = "fast-rng")]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct FastRng;
+
+#[cfg(feature = "fast-rng")]
+impl RngCore for FastRng {
+    fn next_u32(&mut self) -> u32 {
+        rand_core::impls::next_u32_via_fill(self)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        rand_core::impls::next_u64_via_fill(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        FAST_RNG.with(|rng| {
+            rng.borrow_mut()
+                .get_or_insert_with(Reseeding::new)
+                .fill_bytes(dest)
+        })
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+/// A generator seeded by the operating system, see [`Reseeding`].
+trait Reseed: Sized {
+    fn from_os() -> Self;
+
+    fn fill(&mut self, dest: &mut [u8]);
+}
+
+impl Reseed for ChaCha12Rng {
+    fn from_os() -> Self {
+        Self::from_rng(OsRng).expect("failed to read entropy from the operating system")
+    }
+
+    fn fill(&mut self, dest: &mut [u8]) {
+        self.fill_bytes(dest)
+    }
+}
+
+#[cfg(feature = "fast-rng")]
+impl Reseed for nanorand::WyRand {
+    fn from_os() -> Self {
+        Self::new_seed(OsRng.next_u64())
+    }
+
+    fn fill(&mut self, dest: &mut [u8]) {
+        nanorand::Rng::fill_bytes(self, dest)
     }
 }
 
+/// The state behind [`ThreadRng`] and `FastRng`.
+#[derive(Clone)]
+struct Reseeding<R> {
+    rng: R,
+    pid: u32,
+    remaining: usize,
+}
+
+impl<R: Reseed> Reseeding<R> {
+    fn new() -> Self {
+        Self {
+            rng: R::from_os(),
+            pid: process::id(),
+            remaining: RESEED_THRESHOLD,
+        }
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.reserve(dest.len()).fill(dest);
+    }
+
+    /// Returns the generator about to output `len` bytes, reseeded first if needed.
+    fn reserve(&mut self, len: usize) -> &mut R {
+        if self.pid != process::id() || self.remaining < len {
+            *self = Self::new();
+        }
+        self.remaining = self.remaining.saturating_sub(len);
+        &mut self.rng
+    }
+}
+
+/// Returns 128 random bits, see [`fill_random`].
 pub(crate) fn get_random() -> u128 {
-    WyRand::new().generate::<u128>()
+    let mut rand = [0u8; 16];
+    fill_random(std::iter::once(&mut rand));
+    u128::from_le_bytes(rand)
+}
+
+/// Fills every buffer with random bits from the [`ThreadRng`], checking whether
+/// it must be reseeded only once.
+pub(crate) fn fill_random<'a, I>(bufs: I)
+where
+    I: ExactSizeIterator<Item = &'a mut [u8; 16]>,
+{
+    RNG.with(|rng| {
+        let mut rng = rng.borrow_mut();
+        let rng = rng
+            .get_or_insert_with(Reseeding::new)
+            .reserve(bufs.len() * 16);
+        for buf in bufs {
+            rng.fill(buf);
+        }
+    })
 }
 
 #[cfg(test)]
@@ -38,10 +233,126 @@ mod tests {
     use super::*;
     use crate::Variant;
 
+    /// Replays the given bytes, for tests only.
+    struct Replay([u8; 16]);
+
+    impl RngCore for Replay {
+        fn next_u32(&mut self) -> u32 {
+            rand_core::impls::next_u32_via_fill(self)
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            rand_core::impls::next_u64_via_fill(self)
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            dest.copy_from_slice(&self.0[..dest.len()]);
+        }
+
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+            self.fill_bytes(dest);
+            Ok(())
+        }
+    }
+
+    impl CryptoRng for Replay {}
+
     #[test]
     fn uuid_from_random() {
         let uuid = UUID::v4().new();
         assert_eq!(uuid.get_version(), Ok(Version::RAND));
         assert_eq!(uuid.get_variant(), Ok(Variant::RFC4122));
+        assert_ne!(UUID::v4().new(), uuid);
+    }
+
+    #[test]
+    fn uuid_from_given_rng() {
+        let uuid = UUID::v4_with(&mut Replay([0xff; 16])).new();
+        assert_eq!(uuid.to_string(), "ffffffff-ffff-4fff-bfff-ffffffffffff");
+
+        for uuid in [
+            UUID::v4_with(&mut OsRng).new(),
+            UUID::v4_with(&mut ThreadRng).new(),
+        ] {
+            assert_eq!(uuid.get_version(), Ok(Version::RAND));
+            assert_eq!(uuid.get_variant(), Ok(Variant::RFC4122));
+        }
+    }
+
+    #[test]
+    fn fill_many() {
+        let mut uuids = [UUID::NIL; 1_000];
+        UUID::fill_v4(&mut uuids);
+
+        let unique: std::collections::HashSet<_> = uuids.iter().collect();
+        assert_eq!(unique.len(), uuids.len());
+        for uuid in uuids {
+            assert_eq!(uuid.get_version(), Ok(Version::RAND));
+            assert_eq!(uuid.get_variant(), Ok(Variant::RFC4122));
+        }
+
+        UUID::fill_v4(&mut []);
+    }
+
+    #[cfg(feature = "fast-rng")]
+    #[test]
+    fn uuid_from_fast_rng() {
+        let uuid = UUID::v4_fast().new();
+        assert_eq!(uuid.get_version(), Ok(Version::RAND));
+        assert_eq!(uuid.get_variant(), Ok(Variant::RFC4122));
+        assert_ne!(UUID::v4_fast().new(), uuid);
+
+        let there = std::thread::spawn(|| FastRng.next_u64()).join().unwrap();
+        assert_ne!(FastRng.next_u64(), there);
+    }
+
+    #[test]
+    fn thread_rng_differs_across_threads() {
+        let here = ThreadRng.next_u64();
+        let there = std::thread::spawn(|| ThreadRng.next_u64()).join().unwrap();
+        assert_ne!(here, there);
+    }
+
+    fn check_reseed_after_fork<R: Reseed + Clone>() {
+        let mut parent = Reseeding::<R>::new();
+        let mut child = parent.clone();
+        // As seen from the child right after `fork()`.
+        child.pid = parent.pid.wrapping_add(1);
+
+        let (mut a, mut b) = ([0u8; 32], [0u8; 32]);
+        parent.fill_bytes(&mut a);
+        child.fill_bytes(&mut b);
+        assert_ne!(a, b);
+        assert_eq!(child.pid, process::id());
+    }
+
+    #[test]
+    fn reseed_after_fork() {
+        check_reseed_after_fork::<ChaCha12Rng>();
+        #[cfg(feature = "fast-rng")]
+        check_reseed_after_fork::<nanorand::WyRand>();
+    }
+
+    #[test]
+    fn reseed_after_threshold() {
+        let mut rng = Reseeding::<ChaCha12Rng>::new();
+        rng.remaining = 16;
+
+        let mut stale = rng.clone();
+        let (mut a, mut b) = ([0u8; 16], [0u8; 16]);
+        rng.fill_bytes(&mut a);
+        stale.rng.fill_bytes(&mut b);
+        assert_eq!(a, b);
+        assert_eq!(rng.remaining, 0);
+
+        stale = rng.clone();
+        rng.fill_bytes(&mut a);
+        stale.rng.fill_bytes(&mut b);
+        assert_ne!(a, b);
+        assert_eq!(rng.remaining, RESEED_THRESHOLD - 16);
+
+        let mut large = vec![0u8; RESEED_THRESHOLD + 1];
+        rng.fill_bytes(&mut large);
+        assert_eq!(rng.remaining, 0);
     }
 }
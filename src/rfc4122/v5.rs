@@ -2,36 +2,50 @@ use crate::{layout, Layout, Version, UUID};
 
 impl UUID {
     /// Create `UUID` by hashing a namespace identifier and name using SHA1 algorithm.
-    pub fn v5<'a>(data: &str, ns: UUID) -> Layout {
-        let hash = md5::compute(format!("{}", ns) + data).0;
-        layout!(
-            hash[0],
-            hash[1],
-            hash[2],
-            hash[3],
-            hash[4],
-            hash[5],
-            ((Version::SHA1 as u8) << 0x4) | (hash[6] & 0xf),
-            hash[7],
-            hash[8],
-            hash[9],
-            hash[10],
-            hash[11],
-            hash[12],
-            hash[13],
-            hash[14],
-            hash[15]
-        )
+    pub fn v5(data: &str, ns: UUID) -> Layout {
+        Self::v5_bytes(data.as_bytes(), ns)
+    }
+
+    /// Same as [`UUID::v5`], with a name made of arbitrary bytes.
+    ///
+    /// See [`NameBasedBuilder::new_v5`](crate::rfc4122::name::NameBasedBuilder::new_v5) to hash a name piece by piece.
+    pub fn v5_bytes(data: &[u8], ns: UUID) -> Layout {
+        let mut sha1 = sha1::Sha1::new();
+        sha1.update(&ns.0);
+        sha1.update(data);
+        build(sha1.digest().bytes()[..16].try_into().unwrap())
     }
 }
 
+/// Returns the [`Layout`] of a version 5 `UUID` from the SHA1 hash of its name.
+pub(crate) fn build(hash: [u8; 16]) -> Layout {
+    layout!(
+        hash[0],
+        hash[1],
+        hash[2],
+        hash[3],
+        hash[4],
+        hash[5],
+        ((Version::SHA1 as u8) << 0x4) | (hash[6] & 0xf),
+        hash[7],
+        hash[8],
+        hash[9],
+        hash[10],
+        hash[11],
+        hash[12],
+        hash[13],
+        hash[14],
+        hash[15]
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::Variant;
 
     #[test]
-    fn new_uuid_using_md5() {
+    fn new_uuid_using_sha1() {
         let nss = [
             UUID::NAMESPACE_DNS,
             UUID::NAMESPACE_OID,
@@ -45,4 +59,39 @@ mod tests {
             assert_eq!(uuid.get_variant(), Ok(Variant::RFC4122));
         }
     }
+
+    #[test]
+    fn uuid_conformance() {
+        let vectors = [
+            (
+                UUID::NAMESPACE_DNS,
+                "python.org",
+                "886313e1-3b8a-5372-9b90-0c9aee199e5d",
+            ),
+            (
+                UUID::NAMESPACE_DNS,
+                "www.example.com",
+                "2ed6657d-e927-568b-95e1-2665a8aea6a2",
+            ),
+            (
+                UUID::NAMESPACE_URL,
+                "http://python.org/",
+                "4c565f0d-3f5a-5890-b41b-20cf47701c5e",
+            ),
+            (
+                UUID::NAMESPACE_OID,
+                "1.3.6.1",
+                "1447fa61-5277-5fef-a9b3-fbc6e44f4af3",
+            ),
+            (
+                UUID::NAMESPACE_X500,
+                "c=ca",
+                "cc957dd1-a972-5349-98cd-874190002798",
+            ),
+        ];
+
+        for (ns, name, expected) in vectors {
+            assert_eq!(UUID::v5(name, ns).new().to_string(), expected);
+        }
+    }
 }
@@ -0,0 +1,147 @@
+use crate::{layout, rfc4122::v1, Generator, Layout, Node, Version, UUID};
+
+impl UUID {
+    /// Returns the [`Layout`] generated from [`Node`] and a Gregorian timestamp
+    /// stored most significant bits first, so that identifiers sort by time.
+    ///
+    /// Shares its clock sequence with [`UUID::v1`], and delegates to the global [`Generator`].
+    pub fn v6() -> Layout {
+        Generator::with_current(Generator::v6)
+    }
+
+    /// Converts a time-based `UUID` into its time-ordered form.
+    ///
+    /// The conversion is lossless, see [`UUID::to_v1`]. The clock sequence is
+    /// copied as is, along with the variant bits, even if they are not `rfc4122` ones.
+    pub fn to_v6(&self) -> Result<UUID, &str> {
+        match self.get_version() {
+            Ok(Version::TIME) => Ok(self.with_clock_seq_of(
+                build(v1::ticks(self), [self.0[8], self.0[9]], self.get_node()).new(),
+            )),
+            Ok(Version::ORDERED) => Ok(*self),
+            _ => Err("Invalid version"),
+        }
+    }
+
+    /// Converts a time-ordered `UUID` back into its time-based form.
+    ///
+    /// The conversion is lossless, see [`UUID::to_v6`].
+    pub fn to_v1(&self) -> Result<UUID, &str> {
+        match self.get_version() {
+            Ok(Version::ORDERED) => Ok(self.with_clock_seq_of(
+                v1::build(ticks(self), [self.0[8], self.0[9]], self.get_node()).new(),
+            )),
+            Ok(Version::TIME) => Ok(*self),
+            _ => Err("Invalid version"),
+        }
+    }
+
+    /// Returns `uuid` with the bytes 8 and 9 of `self`, which `layout!` would mask.
+    fn with_clock_seq_of(&self, mut uuid: UUID) -> UUID {
+        uuid.0[8..10].copy_from_slice(&self.0[8..10]);
+        uuid
+    }
+}
+
+/// Returns the [`Layout`] of a version 6 `UUID` from a 60-bit Gregorian timestamp.
+pub(crate) fn build(ticks: u64, cs: [u8; 2], node: Node) -> Layout {
+    let th = ((ticks >> 28) as u32).to_be_bytes();
+    let tm = ((ticks >> 12) as u16).to_be_bytes();
+    let tl = (ticks as u16).to_be_bytes();
+    let n = node.0;
+
+    layout!(
+        th[0],
+        th[1],
+        th[2],
+        th[3],
+        tm[0],
+        tm[1],
+        ((Version::ORDERED as u8) << 0x4) | (tl[0] & 0xf),
+        tl[1],
+        cs[0],
+        cs[1],
+        n[0],
+        n[1],
+        n[2],
+        n[3],
+        n[4],
+        n[5]
+    )
+}
+
+/// Returns the 60-bit timestamp of a version 6 `UUID`.
+pub(crate) fn ticks(uuid: &UUID) -> u64 {
+    let b = uuid.0;
+    (u32::from_be_bytes([b[0], b[1], b[2], b[3]]) as u64) << 28
+        | (u16::from_be_bytes([b[4], b[5]]) as u64) << 12
+        | (b[6] as u64 & 0xf) << 8
+        | b[7] as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Variant;
+
+    // Example values from `rfc9562` appendix A.1 and A.5.
+    const V1: UUID = UUID([
+        0xc2, 0x32, 0xab, 0x00, 0x94, 0x14, 0x11, 0xec, 0xb3, 0xc8, 0x9f, 0x6b, 0xde, 0xce, 0xd8,
+        0x46,
+    ]);
+    const V6: UUID = UUID([
+        0x1e, 0xc9, 0x41, 0x4c, 0x23, 0x2a, 0x6b, 0x00, 0xb3, 0xc8, 0x9f, 0x6b, 0xde, 0xce, 0xd8,
+        0x46,
+    ]);
+
+    #[test]
+    fn uuid_from_reordered_time() {
+        let uuid = UUID::v6().new();
+        assert_eq!(uuid.get_version(), Ok(Version::ORDERED));
+        assert_eq!(uuid.get_variant(), Ok(Variant::RFC4122));
+    }
+
+    #[test]
+    fn uuid_rfc9562_example() {
+        let uuid = build(0x1ec9414c232ab00, [0x33, 0xc8], V6.get_node()).new();
+        assert_eq!(uuid, V6);
+        assert_eq!(ticks(&uuid), 0x1ec9414c232ab00);
+        assert_eq!(v1::ticks(&V1), 0x1ec9414c232ab00);
+    }
+
+    #[test]
+    fn convert_between_v1_and_v6() {
+        assert_eq!(V1.to_v6(), Ok(V6));
+        assert_eq!(V6.to_v1(), Ok(V1));
+        assert_eq!(V1.to_v1(), Ok(V1));
+        assert_eq!(V6.to_v6(), Ok(V6));
+
+        let uuid = UUID::v6().new();
+        assert_eq!(uuid.to_v1().unwrap().to_v6(), Ok(uuid));
+    }
+
+    #[test]
+    fn convert_keeps_other_variants() {
+        let mut v1 = V1;
+        v1.0[8] = 0xc3;
+        assert_eq!(v1.get_variant(), Ok(Variant::MS));
+
+        let v6 = v1.to_v6().unwrap();
+        assert_eq!(&v6.0[8..10], &[0xc3, 0xc8]);
+        assert_eq!(v6.to_v1(), Ok(v1));
+    }
+
+    #[test]
+    fn uuid_unique() {
+        let uuids: Vec<_> = (0..1_000).map(|_| UUID::v6().new()).collect();
+        let unique: std::collections::HashSet<_> = uuids.iter().map(|uuid| uuid.0).collect();
+        assert_eq!(unique.len(), uuids.len());
+    }
+
+    #[test]
+    fn convert_other_versions() {
+        let uuid = UUID([0x55; 16]);
+        assert_eq!(uuid.to_v1(), Err("Invalid version"));
+        assert_eq!(uuid.to_v6(), Err("Invalid version"));
+    }
+}
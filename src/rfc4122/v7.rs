@@ -0,0 +1,171 @@
+#![cfg(feature = "rand")]
+
+use std::sync::Arc;
+
+use crate::{clock::Clock, layout, Generator, Layout, Version, UUID};
+
+/// Largest value of the 12-bit counter stored in `rand_a`.
+const MAX_COUNTER: u16 = 0xfff;
+
+/// Keeps [`UUID::v7`] monotonic within one process, see `rfc9562` section 6.2.
+pub(crate) struct Context {
+    pub(crate) clock: Arc<dyn Clock>,
+    millis: u64,
+    counter: u16,
+}
+
+impl Context {
+    pub(crate) fn new(clock: Arc<dyn Clock>) -> Self {
+        Self {
+            clock,
+            millis: 0,
+            counter: 0,
+        }
+    }
+
+    /// Returns the next `(millis, counter)` pair for the current time of the clock.
+    pub(crate) fn tick(&mut self, seed: u16) -> (u64, u16) {
+        let millis = self.clock.now().to_unix_millis();
+        self.next(millis, seed)
+    }
+
+    /// Returns the next `(millis, counter)` pair, never going backwards.
+    ///
+    /// The counter is reseeded with its most significant bit cleared whenever
+    /// the clock ticks, leaving room for at least 2048 increments. Once it
+    /// overflows, the timestamp is borrowed from the next millisecond.
+    pub(crate) fn next(&mut self, millis: u64, seed: u16) -> (u64, u16) {
+        if millis > self.millis {
+            self.millis = millis;
+            self.counter = seed & (MAX_COUNTER >> 1);
+        } else if self.counter == MAX_COUNTER {
+            self.millis += 1;
+            self.counter = seed & (MAX_COUNTER >> 1);
+        } else {
+            self.counter += 1;
+        }
+
+        (self.millis, self.counter)
+    }
+}
+
+impl UUID {
+    /// Creates a time-ordered `UUID` from the milliseconds elapsed since the Unix epoch.
+    ///
+    /// Identifiers generated by the same process are strictly increasing.
+    /// The time is read from the [`clock`](crate::clock).
+    ///
+    /// Delegates to the global [`Generator`].
+    pub fn v7() -> Layout {
+        Generator::with_current(Generator::v7)
+    }
+
+    /// Overwrites every [`UUID`] of `uuids` with a time-ordered one, like
+    /// [`UUID::v7`] but locking the generator and reading the clock only once.
+    ///
+    /// The identifiers are strictly increasing along the slice.
+    pub fn fill_v7(uuids: &mut [UUID]) {
+        Generator::with_current(|generator| generator.fill_v7(uuids))
+    }
+}
+
+/// Returns the [`Layout`] of a version 7 `UUID` with `rand_b` taken from `rand`.
+pub(crate) fn build(millis: u64, counter: u16, rand: [u8; 16]) -> Layout {
+    let ms = millis.to_be_bytes();
+    let c = counter.to_be_bytes();
+
+    layout!(
+        ms[2],
+        ms[3],
+        ms[4],
+        ms[5],
+        ms[6],
+        ms[7],
+        ((Version::UNIX as u8) << 0x4) | (c[0] & 0xf),
+        c[1],
+        rand[8],
+        rand[9],
+        rand[10],
+        rand[11],
+        rand[12],
+        rand[13],
+        rand[14],
+        rand[15]
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{clock::SystemClock, Variant};
+
+    #[test]
+    fn uuid_from_unix_time() {
+        let uuid = UUID::v7().new();
+        assert_eq!(uuid.get_version(), Ok(Version::UNIX));
+        assert_eq!(uuid.get_variant(), Ok(Variant::RFC4122));
+    }
+
+    #[test]
+    fn uuid_rfc9562_example() {
+        let rand = [
+            0, 0, 0, 0, 0, 0, 0, 0, 0x18, 0xc4, 0xdc, 0x0c, 0x0c, 0x07, 0x39, 0x8f,
+        ];
+        let uuid = build(0x017f22e279b0, 0xcc3, rand).new();
+        assert_eq!(uuid.to_string(), "017f22e2-79b0-7cc3-98c4-dc0c0c07398f");
+    }
+
+    #[test]
+    fn uuid_is_monotonic() {
+        let mut prev = UUID::v7().new();
+        for _ in 0..10_000 {
+            let uuid = UUID::v7().new();
+            assert!(uuid.0 > prev.0, "{} <= {}", uuid, prev);
+            prev = uuid;
+        }
+    }
+
+    #[test]
+    fn fill_is_strictly_increasing() {
+        let mut uuids = [UUID::NIL; 10_000];
+        UUID::fill_v7(&mut uuids);
+
+        for pair in uuids.windows(2) {
+            assert!(pair[0] < pair[1], "{} >= {}", pair[0], pair[1]);
+        }
+        for uuid in uuids {
+            assert_eq!(uuid.get_version(), Ok(Version::UNIX));
+            assert_eq!(uuid.get_variant(), Ok(Variant::RFC4122));
+        }
+        assert!(UUID::v7().new() > uuids[uuids.len() - 1]);
+    }
+
+    #[test]
+    fn context_borrows_next_millisecond() {
+        let mut ctx = Context::new(Arc::new(SystemClock));
+
+        assert_eq!(ctx.next(10, 0xffff), (10, 0x7ff));
+        assert_eq!(ctx.next(10, 0), (10, 0x800));
+        assert_eq!(ctx.next(9, 0), (10, 0x801));
+
+        ctx.counter = MAX_COUNTER;
+        assert_eq!(ctx.next(10, 0x0123), (11, 0x123));
+        assert_eq!(ctx.next(11, 0), (11, 0x124));
+    }
+
+    #[test]
+    fn context_reads_its_clock() {
+        use crate::{clock::FixedClock, Timestamp};
+
+        let ts = Timestamp::from_unix_millis(0x017f22e279b0);
+        let mut ctx = Context::new(Arc::new(FixedClock(ts)));
+
+        let (millis, counter) = ctx.tick(0x0cc3);
+        assert_eq!((millis, counter), (0x017f22e279b0, 0x4c3));
+        assert_eq!(
+            build(millis, counter, [0; 16]).new().get_timestamp(),
+            Some(ts)
+        );
+        assert_eq!(ctx.tick(0), (0x017f22e279b0, 0x4c4));
+    }
+}
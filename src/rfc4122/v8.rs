@@ -0,0 +1,53 @@
+use crate::{layout, Layout, Version, UUID};
+
+impl UUID {
+    /// Creates a `UUID` from vendor-specific data.
+    ///
+    /// Only the version and variant bits of `bytes` are overwritten, the
+    /// remaining 122 bits are left to the caller's own layout.
+    pub fn v8(bytes: [u8; 16]) -> Layout {
+        layout!(
+            bytes[0],
+            bytes[1],
+            bytes[2],
+            bytes[3],
+            bytes[4],
+            bytes[5],
+            ((Version::CUSTOM as u8) << 0x4) | (bytes[6] & 0xf),
+            bytes[7],
+            bytes[8],
+            bytes[9],
+            bytes[10],
+            bytes[11],
+            bytes[12],
+            bytes[13],
+            bytes[14],
+            bytes[15]
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Variant;
+
+    #[test]
+    fn uuid_from_custom_data() {
+        for byte in [u8::MIN, 0x5a, u8::MAX] {
+            let uuid = UUID::v8([byte; 16]).new();
+            assert_eq!(uuid.get_version(), Ok(Version::CUSTOM));
+            assert_eq!(uuid.get_variant(), Ok(Variant::RFC4122));
+        }
+    }
+
+    #[test]
+    fn uuid_keeps_custom_bits() {
+        let bytes = [
+            0x2e, 0x5a, 0x01, 0x2f, 0xd4, 0x4c, 0x0b, 0xcd, 0x3e, 0x65, 0x31, 0x73, 0x72, 0x74,
+            0x75, 0x76,
+        ];
+        let uuid = UUID::v8(bytes).new();
+        assert_eq!(uuid.to_string(), "2e5a012f-d44c-8bcd-be65-317372747576");
+    }
+}
@@ -0,0 +1,246 @@
+use core::{marker::PhantomData, ops::Deref};
+use std::sync::Arc;
+
+use rand_core::RngCore;
+
+use crate::{
+    clock::{Clock, FixedClock},
+    Generator, Layout, Node, Timestamp, UUID,
+};
+
+/// Time at which a [`SeededGenerator`] is stopped unless given another [`Clock`],
+/// see `rfc9562` appendix A.
+const START: Timestamp = Timestamp::from_unix(1_645_557_742, 0);
+
+/// A [`Generator`] producing the same sequence of valid [`UUID`]s for a given
+/// seed, in tests or when generating fixtures.
+///
+/// The node, clock sequence and random bits are all derived from the seed, and
+/// the time is read from a stopped clock, see [`SeededGenerator::with_clock`].
+/// Code calling the free functions like [`UUID::v4`] can be pointed at it with
+/// [`SeededGenerator::set_default`].
+///
+/// The sequence is predictable by design, never use it in production.
+pub struct SeededGenerator(Arc<Generator>);
+
+impl SeededGenerator {
+    /// Returns a [`SeededGenerator`] whose output only depends on `seed`.
+    pub fn new(seed: u64) -> Self {
+        let mut rng = SplitMix64(seed);
+        let rand = rng.next_u64().to_le_bytes();
+        let node = Node([rand[0] | 0x01, rand[1], rand[2], rand[3], rand[4], rand[5]]);
+
+        Self(Arc::new(
+            Generator::builder()
+                .node(node)
+                .clock(FixedClock(START))
+                .clock_seq(u16::from_le_bytes([rand[6], rand[7]]))
+                .any_rng(rng)
+                .finish(),
+        ))
+    }
+
+    /// Reads the time from `clock` instead, e.g. a [`MockClock`](crate::clock::MockClock)
+    /// to control the timestamps.
+    pub fn with_clock<C: Clock + 'static>(self, clock: C) -> Self {
+        self.0.set_clock(Arc::new(clock));
+        self
+    }
+
+    /// Returns [`UUID::v8`] with random custom bits.
+    pub fn v8(&self) -> Layout {
+        UUID::v8(self.0.random_bytes())
+    }
+
+    /// Makes [`UUID::v1`], [`UUID::v4`] and the other free functions delegate to
+    /// this generator on the current thread, until the returned guard is dropped.
+    ///
+    /// Other threads, including tests running in parallel, keep using the global
+    /// [`Generator`].
+    pub fn set_default(&self) -> DefaultGuard {
+        DefaultGuard {
+            previous: Generator::set_current(Some(Arc::clone(&self.0))),
+            _thread: PhantomData,
+        }
+    }
+}
+
+/// Restores the previous generator of the current thread when dropped, see
+/// [`SeededGenerator::set_default`].
+#[must_use = "the generator is restored as soon as the guard is dropped"]
+pub struct DefaultGuard {
+    previous: Option<Arc<Generator>>,
+    /// The override is local to the thread that set it.
+    _thread: PhantomData<*const ()>,
+}
+
+impl Drop for DefaultGuard {
+    fn drop(&mut self) {
+        Generator::set_current(self.previous.take());
+    }
+}
+
+/// Gives access to [`Generator::v1`], [`Generator::v4`] and the other versions.
+impl Deref for SeededGenerator {
+    type Target = Generator;
+
+    fn deref(&self) -> &Generator {
+        &self.0
+    }
+}
+
+/// A tiny generator with a 64-bit state, good enough to spread a seed.
+///
+/// See <https://prng.di.unimi.it/splitmix64.c>.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    /// Returns 128 random bits: the first `u64` drawn fills the last eight bytes and
+    /// the second one the first eight, both little-endian.
+    fn next_bytes(&mut self) -> [u8; 16] {
+        let high = self.next_u64() as u128;
+        let low = self.next_u64() as u128;
+        (high << 64 | low).to_le_bytes()
+    }
+}
+
+impl RngCore for SplitMix64 {
+    fn next_u32(&mut self) -> u32 {
+        self.next_u64() as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(16) {
+            let rand = self.next_bytes();
+            chunk.copy_from_slice(&rand[..chunk.len()]);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{clock::MockClock, Variant, Version};
+    use std::time::Duration;
+
+    fn sequence(seed: u64) -> Vec<UUID> {
+        let gen = SeededGenerator::new(seed);
+        (0..100)
+            .flat_map(|_| {
+                [
+                    gen.v1().new(),
+                    gen.v4().new(),
+                    gen.v6().new(),
+                    gen.v7().new(),
+                    gen.v8().new(),
+                ]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn same_seed_same_sequence() {
+        assert_eq!(sequence(42), sequence(42));
+        assert_ne!(sequence(42), sequence(43));
+
+        let unique: std::collections::HashSet<_> = sequence(42).into_iter().collect();
+        assert_eq!(unique.len(), 500);
+    }
+
+    #[test]
+    fn sequence_is_stable() {
+        let gen = SeededGenerator::new(0);
+        assert_eq!(
+            gen.v4().new().to_string(),
+            "4f450980-185d-4406-b465-b9a16a9e786e"
+        );
+        assert_eq!(
+            gen.v1().new().to_string(),
+            "c232ab00-9414-11ec-a220-afcd1d7b39a8"
+        );
+    }
+
+    #[test]
+    fn free_functions_use_default() {
+        let expected = {
+            let gen = SeededGenerator::new(3);
+            [
+                gen.v1().new(),
+                gen.v4().new(),
+                gen.v6().new(),
+                gen.v7().new(),
+            ]
+        };
+
+        let gen = SeededGenerator::new(3);
+        {
+            let _guard = gen.set_default();
+            let nested = SeededGenerator::new(4);
+            drop(nested.set_default());
+
+            assert_eq!(
+                [
+                    UUID::v1().new(),
+                    UUID::v4().new(),
+                    UUID::v6().new(),
+                    UUID::v7().new()
+                ],
+                expected
+            );
+            // Other threads are left alone.
+            let there = std::thread::spawn(|| UUID::v4().new()).join().unwrap();
+            assert_ne!(there, expected[1]);
+        }
+        assert_ne!(UUID::v4().new(), SeededGenerator::new(3).v4().new());
+    }
+
+    #[test]
+    fn sequence_has_valid_versions() {
+        let versions = [
+            Version::TIME,
+            Version::RAND,
+            Version::ORDERED,
+            Version::UNIX,
+            Version::CUSTOM,
+        ];
+
+        for (i, uuid) in sequence(7).iter().enumerate() {
+            assert_eq!(uuid.get_version(), Ok(versions[i % 5]), "{}", uuid);
+            assert_eq!(uuid.get_variant(), Ok(Variant::RFC4122), "{}", uuid);
+        }
+    }
+
+    #[test]
+    fn time_based_follow_the_clock() {
+        let gen = SeededGenerator::new(7);
+        assert_eq!(gen.v7().new().get_timestamp(), Some(START));
+
+        let mut prev = gen.v7().new();
+        for _ in 0..10_000 {
+            let uuid = gen.v7().new();
+            assert!(uuid > prev, "{} <= {}", uuid, prev);
+            prev = uuid;
+        }
+
+        let clock = Arc::new(MockClock::new(START));
+        let gen = SeededGenerator::new(7).with_clock(Arc::clone(&clock));
+        clock.advance(Duration::from_secs(60));
+        assert_eq!(
+            gen.v6().new().get_timestamp(),
+            Some(START + Duration::from_secs(60))
+        );
+    }
+}
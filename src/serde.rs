@@ -0,0 +1,213 @@
+//! Serde support for [`UUID`].
+//!
+//! By default a [`UUID`] is serialized as a hyphenated string in human-readable
+//! formats like JSON, and as 16 raw bytes in binary ones like bincode. Both
+//! representations, as well as the other textual [`Format`]s, are accepted when
+//! deserializing.
+//!
+//! The modules below pin a representation for a single field:
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//! use unik::UUID;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Row {
+//!     #[serde(with = "unik::serde::simple")]
+//!     id: UUID,
+//!     #[serde(with = "unik::serde::urn")]
+//!     parent: UUID,
+//! }
+//! ```
+
+use core::fmt;
+
+use ::serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{adapter::Hyphenated, Format, UUID};
+
+impl Serialize for UUID {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(self.hyphenated().encode(&mut [0u8; Hyphenated::LENGTH]))
+        } else {
+            serializer.serialize_bytes(&self.0)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for UUID {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let visitor = Visitor {
+            formats: &Format::ALL,
+        };
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(visitor)
+        } else {
+            deserializer.deserialize_bytes(visitor)
+        }
+    }
+}
+
+/// Accepts a string in one of `formats`, or 16 bytes.
+struct Visitor {
+    formats: &'static [Format],
+}
+
+impl<'de> de::Visitor<'de> for Visitor {
+    type Value = UUID;
+
+    fn expecting(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "a UUID string or 16 bytes")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<UUID, E> {
+        UUID::parse_only(v, self.formats).map_err(E::custom)
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<UUID, E> {
+        <[u8; 16]>::try_from(v)
+            .map(UUID)
+            .map_err(|_| E::invalid_length(v.len(), &self))
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<UUID, A::Error> {
+        let mut bytes = [0u8; 16];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+        }
+        if seq.next_element::<u8>()?.is_some() {
+            return Err(de::Error::invalid_length(17, &self));
+        }
+
+        Ok(UUID(bytes))
+    }
+}
+
+/// Serializes a [`UUID`] as 32 hex digits, see [`Format::Simple`].
+pub mod simple {
+    use super::*;
+    use crate::adapter::Simple;
+
+    pub fn serialize<S: Serializer>(uuid: &UUID, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(uuid.simple().encode(&mut [0u8; Simple::LENGTH]))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<UUID, D::Error> {
+        deserializer.deserialize_str(Visitor {
+            formats: &[Format::Simple],
+        })
+    }
+}
+
+/// Serializes a [`UUID`] prefixed by `urn:uuid:`, see [`Format::Urn`].
+pub mod urn {
+    use super::*;
+    use crate::adapter::Urn;
+
+    pub fn serialize<S: Serializer>(uuid: &UUID, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(uuid.urn().encode(&mut [0u8; Urn::LENGTH]))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<UUID, D::Error> {
+        deserializer.deserialize_str(Visitor {
+            formats: &[Format::Urn],
+        })
+    }
+}
+
+/// Serializes a [`UUID`] as 16 raw bytes, even in human-readable formats.
+pub mod compact {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(uuid: &UUID, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&uuid.0)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<UUID, D::Error> {
+        deserializer.deserialize_bytes(Visitor { formats: &[] })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::uuid;
+    use ::serde::{Deserialize, Serialize};
+
+    const ID: UUID = uuid!("67e55044-10b1-426f-9247-bb680e5fe0c8");
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Row {
+        id: UUID,
+        #[serde(with = "crate::serde::simple")]
+        simple: UUID,
+        #[serde(with = "crate::serde::urn")]
+        urn: UUID,
+        #[serde(with = "crate::serde::compact")]
+        compact: UUID,
+    }
+
+    const ROW: Row = Row {
+        id: ID,
+        simple: ID,
+        urn: ID,
+        compact: ID,
+    };
+
+    #[test]
+    fn serde_human_readable() {
+        let json = serde_json::to_string(&ID).unwrap();
+        assert_eq!(json, "\"67e55044-10b1-426f-9247-bb680e5fe0c8\"");
+        assert_eq!(serde_json::from_str::<UUID>(&json).unwrap(), ID);
+
+        for json in [
+            "\"67E5504410B1426F9247BB680E5FE0C8\"",
+            "\"{67e55044-10b1-426f-9247-bb680e5fe0c8}\"",
+            "\"urn:uuid:67e55044-10b1-426f-9247-bb680e5fe0c8\"",
+        ] {
+            assert_eq!(serde_json::from_str::<UUID>(json).unwrap(), ID);
+        }
+
+        let err = serde_json::from_str::<UUID>("\"67e55044\"").unwrap_err();
+        assert!(err.to_string().starts_with("invalid length"), "{}", err);
+    }
+
+    #[test]
+    fn serde_binary() {
+        let bytes = bincode::serialize(&ID).unwrap();
+        assert_eq!(bytes.len(), 8 + 16);
+        assert_eq!(&bytes[8..], &ID.0);
+        assert_eq!(bincode::deserialize::<UUID>(&bytes).unwrap(), ID);
+
+        let short = bincode::serialize(&[0u8; 15][..]).unwrap();
+        assert!(bincode::deserialize::<UUID>(&short).is_err());
+    }
+
+    #[test]
+    fn serde_with_modules() {
+        let json = serde_json::to_value(&ROW).unwrap();
+        assert_eq!(json["id"], "67e55044-10b1-426f-9247-bb680e5fe0c8");
+        assert_eq!(json["simple"], "67e5504410b1426f9247bb680e5fe0c8");
+        assert_eq!(json["urn"], "urn:uuid:67e55044-10b1-426f-9247-bb680e5fe0c8");
+        assert_eq!(json["compact"], serde_json::json!(ID.0));
+        assert_eq!(serde_json::from_value::<Row>(json).unwrap(), ROW);
+
+        let bytes = bincode::serialize(&ROW).unwrap();
+        assert_eq!(bincode::deserialize::<Row>(&bytes).unwrap(), ROW);
+    }
+
+    #[test]
+    fn serde_with_modules_are_strict() {
+        let mut json = serde_json::to_value(&ROW).unwrap();
+        json["simple"] = "67e55044-10b1-426f-9247-bb680e5fe0c8".into();
+        assert!(serde_json::from_value::<Row>(json).is_err());
+
+        let mut json = serde_json::to_value(&ROW).unwrap();
+        json["urn"] = "67e55044-10b1-426f-9247-bb680e5fe0c8".into();
+        assert!(serde_json::from_value::<Row>(json).is_err());
+    }
+}
@@ -0,0 +1,430 @@
+//! Stable storage for the state of the time-based generators, see `rfc4122` section 4.2.1.
+//!
+//! By default [`UUID::v1`](crate::UUID::v1) and [`UUID::v6`](crate::UUID::v6)
+//! only remember their state in memory, which is lost on restart. Installing a
+//! [`Storage`] with [`set_storage`] makes them reload it on startup and share it
+//! with other processes on the same host.
+
+use std::{
+    fs::OpenOptions,
+    io::{self, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use crate::{
+    clock::{Clock, SystemClock},
+    Generator, Node,
+};
+
+/// The last timestamp, clock sequence and node used by the time-based generators.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct State {
+    /// Count of 100-ns intervals since the Gregorian epoch.
+    pub ticks: u64,
+    /// Last reading of the clock, in the same unit as `ticks`. It lags behind
+    /// `ticks` when several identifiers were requested within the same tick.
+    pub seen: u64,
+    /// The 14-bit clock sequence.
+    pub clock_seq: u16,
+    pub node: Node,
+}
+
+/// A place to save [`State`] across restarts.
+pub trait Storage: Send {
+    /// Reads the saved [`State`], passes it to `f` and saves the returned one.
+    ///
+    /// Implementations must hold exclusive access to the underlying storage for
+    /// the whole call, so that processes sharing it never hand out the same state.
+    fn update(&mut self, f: &mut dyn FnMut(Option<State>) -> State) -> io::Result<State>;
+}
+
+/// Saves [`State`] to a file, locked for the duration of each update.
+#[derive(Debug)]
+pub struct FileStorage {
+    path: PathBuf,
+}
+
+impl FileStorage {
+    /// Returns a [`FileStorage`] saving to `path`, which is created on first use.
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+}
+
+impl Storage for FileStorage {
+    fn update(&mut self, f: &mut dyn FnMut(Option<State>) -> State) -> io::Result<State> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&self.path)?;
+        file.lock()?;
+
+        let mut buf = String::new();
+        file.read_to_string(&mut buf)?;
+
+        // A corrupted file is handled the same way as a missing one.
+        let state = f(parse(&buf));
+
+        file.set_len(0)?;
+        file.seek(SeekFrom::Start(0))?;
+        writeln!(
+            file,
+            "{} {} {} {}",
+            state.ticks, state.clock_seq, state.node, state.seen
+        )?;
+
+        Ok(state)
+    }
+}
+
+fn parse(buf: &str) -> Option<State> {
+    let mut fields = buf.split_whitespace();
+    let ticks = fields.next()?.parse().ok()?;
+    let clock_seq = fields.next()?.parse().ok()?;
+
+    let mut node = [0u8; 6];
+    let mut octets = fields.next()?.split('-');
+    for byte in node.iter_mut() {
+        *byte = u8::from_str_radix(octets.next()?, 16).ok()?;
+    }
+
+    // Files written before the clock reading was saved hold the issued timestamp only.
+    let seen = match fields.next() {
+        Some(seen) => seen.parse().ok()?,
+        None => ticks,
+    };
+
+    Some(State {
+        ticks,
+        seen,
+        clock_seq,
+        node: Node(node),
+    })
+}
+
+/// Makes the global [`Generator`] save its state to `storage`, see
+/// [`GeneratorBuilder::storage`](crate::GeneratorBuilder::storage) for other instances.
+///
+/// The saved state is reloaded right away. If it is missing, a random clock
+/// sequence is used. If it was written with another node, or the clock has
+/// gone backwards since, the clock sequence is incremented.
+///
+/// When `storage` later fails, [`UUID::v1`](crate::UUID::v1) and [`UUID::v6`](crate::UUID::v6)
+/// keep going from the state held in memory, which is only unique within the current
+/// process. [`Generator::try_v1`] and [`Generator::try_v6`] return the error instead.
+pub fn set_storage<S: Storage + 'static>(storage: S) -> io::Result<()> {
+    let mut ctx = Generator::global().v1.lock().unwrap();
+    ctx.set_storage(Box::new(storage))
+}
+
+/// Tracks the last timestamp, clock sequence and node, see `rfc4122` section 4.2.1.
+pub(crate) struct Context {
+    /// Node of the current process, looked up on first use, see [`Context::node`].
+    pub(crate) node: Option<Node>,
+    pub(crate) clock: Arc<dyn Clock>,
+    /// Last state written into a [`UUID`](crate::UUID).
+    state: State,
+    storage: Option<Box<dyn Storage>>,
+}
+
+impl Context {
+    /// Returns a [`Context`] stamping `node`, or the default [`Node`] if `None`.
+    pub(crate) fn new(node: Option<Node>, clock_seq: u16) -> Self {
+        Self {
+            node,
+            clock: Arc::new(SystemClock),
+            state: State {
+                ticks: 0,
+                seen: 0,
+                clock_seq: clock_seq & 0x3fff,
+                // Replaced once the node is looked up, unless a state was issued or loaded since.
+                node: node.unwrap_or(Node([0u8; 6])),
+            },
+            storage: None,
+        }
+    }
+
+    /// Returns the node, looking up the default one on first use only, so that
+    /// generators which never stamp it, e.g. for versions 4 and 7, never read the
+    /// MAC address.
+    pub(crate) fn node(&mut self) -> Node {
+        if let Some(node) = self.node {
+            return node;
+        }
+
+        let node = Node::default();
+        self.node = Some(node);
+        if self.state.ticks == 0 && self.state.seen == 0 {
+            self.state.node = node;
+        }
+        node
+    }
+
+    /// Returns the `(ticks, clock_seq)` pair for the current time of the clock.
+    pub(crate) fn tick(&mut self) -> (u64, u16) {
+        let ticks = self.clock.now().to_gregorian();
+        self.next(ticks)
+    }
+
+    /// Like [`Context::tick`], but returns the error of the storage.
+    pub(crate) fn try_tick(&mut self) -> io::Result<(u64, u16)> {
+        let ticks = self.clock.now().to_gregorian();
+        self.try_next(ticks)
+    }
+
+    /// Returns the `(ticks, clock_seq)` pair to stamp into the next [`UUID`](crate::UUID),
+    /// synchronized with the storage if any, or only with the state held in memory
+    /// if the storage fails.
+    pub(crate) fn next(&mut self, ticks: u64) -> (u64, u16) {
+        self.try_next(ticks).unwrap_or_else(|_| self.advance(ticks))
+    }
+
+    /// Like [`Context::next`], but returns the error of the storage.
+    pub(crate) fn try_next(&mut self, ticks: u64) -> io::Result<(u64, u16)> {
+        let Some(mut storage) = self.storage.take() else {
+            return Ok(self.advance(ticks));
+        };
+
+        let mut issued = None;
+        let res = storage.update(&mut |saved| {
+            self.sync(saved);
+            issued = Some(self.advance(ticks));
+            self.state
+        });
+        self.storage = Some(storage);
+
+        res?;
+        Ok(issued.unwrap_or_else(|| self.advance(ticks)))
+    }
+
+    /// Saves the state to `storage` from now on, after reloading it.
+    pub(crate) fn set_storage(&mut self, storage: Box<dyn Storage>) -> io::Result<()> {
+        self.storage = Some(storage);
+        self.reload()
+    }
+
+    fn reload(&mut self) -> io::Result<()> {
+        let Some(mut storage) = self.storage.take() else {
+            return Ok(());
+        };
+
+        let res = storage.update(&mut |saved| {
+            self.sync(saved);
+            self.state
+        });
+        self.storage = Some(storage);
+
+        res.map(|_| ())
+    }
+
+    /// Adopts the saved state when another process has changed it.
+    fn sync(&mut self, saved: Option<State>) {
+        if let Some(saved) = saved {
+            self.state = saved;
+        }
+    }
+
+    /// The clock sequence is incremented when the clock goes backwards or the
+    /// node changes. Several identifiers requested within the same tick get
+    /// consecutive timestamps instead, as if the clock had a finer resolution.
+    fn advance(&mut self, ticks: u64) -> (u64, u16) {
+        let node = self.node();
+        let state = &mut self.state;
+
+        if ticks < state.seen || node != state.node {
+            state.clock_seq = (state.clock_seq + 1) & 0x3fff;
+            state.ticks = ticks;
+            state.node = node;
+        } else if ticks <= state.ticks {
+            state.ticks += 1;
+        } else {
+            state.ticks = ticks;
+        }
+        state.seen = ticks;
+
+        (state.ticks, state.clock_seq)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("unik-{}-{}", name, std::process::id()))
+    }
+
+    #[test]
+    fn context_bumps_clock_seq() {
+        let mut ctx = Context::new(Some(Node([0x01; 6])), 0x3ffe);
+
+        assert_eq!(ctx.next(100), (100, 0x3ffe));
+        // Same tick, the timestamp is simulated.
+        assert_eq!(ctx.next(100), (101, 0x3ffe));
+        assert_eq!(ctx.next(100), (102, 0x3ffe));
+        assert_eq!(ctx.next(200), (200, 0x3ffe));
+        // The clock went backwards.
+        assert_eq!(ctx.next(150), (150, 0x3fff));
+        // The node changed, the clock sequence wraps around 14 bits.
+        ctx.node = Some(Node([0x02; 6]));
+        assert_eq!(ctx.next(160), (160, 0x0000));
+        assert_eq!(ctx.state.node, Node([0x02; 6]));
+    }
+
+    #[test]
+    fn context_looks_up_node_on_first_use() {
+        let mut ctx = Context::new(None, 0x1234);
+        assert_eq!(ctx.next(100), (100, 0x1234));
+        assert_eq!(Some(ctx.state.node), ctx.node);
+    }
+
+    #[test]
+    fn context_reads_its_clock() {
+        use crate::{clock::MockClock, Timestamp};
+        use std::time::Duration;
+
+        let clock = Arc::new(MockClock::new(Timestamp::from_gregorian(1_000)));
+        let mut ctx = Context::new(Some(Node([0x01; 6])), 0x1234);
+        ctx.clock = clock.clone();
+
+        assert_eq!(ctx.tick(), (1_000, 0x1234));
+        assert_eq!(ctx.tick(), (1_001, 0x1234));
+        clock.advance(Duration::from_micros(1));
+        assert_eq!(ctx.tick(), (1_010, 0x1234));
+        clock.set(Timestamp::from_gregorian(500));
+        assert_eq!(ctx.tick(), (500, 0x1235));
+    }
+
+    #[test]
+    fn file_storage_round_trip() {
+        let path = temp_path("round-trip");
+        let state = State {
+            ticks: 0x1ec9414c232ab00,
+            seen: 0x1ec9414c232aaff,
+            clock_seq: 0x33c8,
+            node: Node([0x9f, 0x6b, 0xde, 0xce, 0xd8, 0x46]),
+        };
+
+        let mut storage = FileStorage::new(&path);
+        assert_eq!(
+            storage.update(&mut |saved| saved.unwrap_or(state)).unwrap(),
+            state
+        );
+        assert_eq!(storage.update(&mut |saved| saved.unwrap()).unwrap(), state);
+
+        fs::write(&path, "garbage").unwrap();
+        assert_eq!(
+            storage
+                .update(&mut |saved| {
+                    assert_eq!(saved, None);
+                    state
+                })
+                .unwrap(),
+            state
+        );
+
+        // Written before the clock reading was saved.
+        let saved = parse("100 5 01-02-03-04-05-06\n").unwrap();
+        assert_eq!((saved.ticks, saved.seen), (100, 100));
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn context_reloads_saved_state() {
+        let path = temp_path("reload");
+        let node = Node([0x01; 6]);
+
+        let mut ctx = Context::new(Some(node), 0x1234);
+        ctx.storage = Some(Box::new(FileStorage::new(&path)));
+        ctx.reload().unwrap();
+        let (ticks, clock_seq) = ctx.next(1_000);
+        assert_eq!(ticks, 1_000);
+
+        // Restarted with a clock that went backwards.
+        let mut ctx = Context::new(Some(node), 0x1234);
+        ctx.storage = Some(Box::new(FileStorage::new(&path)));
+        ctx.reload().unwrap();
+        assert_eq!(ctx.next(900), (900, (clock_seq + 1) & 0x3fff));
+
+        // Restarted with another node.
+        let mut ctx = Context::new(Some(Node([0x02; 6])), 0x1234);
+        ctx.storage = Some(Box::new(FileStorage::new(&path)));
+        ctx.reload().unwrap();
+        assert_eq!(ctx.next(2_000), (2_000, (clock_seq + 2) & 0x3fff));
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn context_follows_clock_of_others() {
+        let path = temp_path("simulated");
+        let node = Node([0x01; 6]);
+
+        let mut first = Context::new(Some(node), 0x1234);
+        first.storage = Some(Box::new(FileStorage::new(&path)));
+        assert_eq!(first.next(1_000), (1_000, 0x1234));
+        assert_eq!(first.next(1_000), (1_001, 0x1234));
+        assert_eq!(first.next(1_000), (1_002, 0x1234));
+
+        // Behind the simulated timestamps, but not behind the clock.
+        let mut second = Context::new(Some(node), 0x1234);
+        second.storage = Some(Box::new(FileStorage::new(&path)));
+        assert_eq!(second.next(1_001), (1_003, 0x1234));
+        assert_eq!(first.next(900), (900, 0x1235));
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    struct FailingStorage;
+
+    impl Storage for FailingStorage {
+        fn update(&mut self, _: &mut dyn FnMut(Option<State>) -> State) -> io::Result<State> {
+            Err(io::Error::other("unavailable"))
+        }
+    }
+
+    #[test]
+    fn context_reports_storage_errors() {
+        let mut ctx = Context::new(Some(Node([0x01; 6])), 0x1234);
+        ctx.storage = Some(Box::new(FailingStorage));
+
+        let err = ctx.try_next(1_000).unwrap_err();
+        assert_eq!(err.to_string(), "unavailable");
+        // Carries on from the state held in memory.
+        assert_eq!(ctx.next(1_000), (1_000, 0x1234));
+        assert_eq!(ctx.next(1_000), (1_001, 0x1234));
+        assert!(ctx.try_next(1_000).is_err());
+    }
+
+    #[test]
+    fn contexts_share_file_storage() {
+        let path = temp_path("share");
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let path = path.clone();
+                std::thread::spawn(move || {
+                    let mut ctx = Context::new(Some(Node([0x01; 6])), 0x1234);
+                    ctx.storage = Some(Box::new(FileStorage::new(path)));
+                    // Every context sees the same frozen clock.
+                    (0..250).map(|_| ctx.next(1_000 + i)).collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        let mut issued = std::collections::HashSet::new();
+        for handle in handles {
+            for pair in handle.join().unwrap() {
+                assert!(issued.insert(pair), "{:?}", pair);
+            }
+        }
+
+        fs::remove_file(&path).unwrap();
+    }
+}
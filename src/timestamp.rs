@@ -0,0 +1,371 @@
+use core::{
+    fmt,
+    ops::{Add, AddAssign, Sub, SubAssign},
+};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[cfg(feature = "utc")]
+use crate::Utc;
+
+const NANOS_PER_SEC: u32 = 1_000_000_000;
+
+/// Number of seconds between the Gregorian epoch (1582-10-15) and the Unix epoch.
+const GREGORIAN_OFFSET_SECS: i64 = 12_219_292_800;
+
+/// A point in time, as whole seconds and sub-second nanoseconds since the Unix epoch.
+///
+/// Seconds are signed, so that every timestamp of a version 1 or 6 [`UUID`](crate::UUID)
+/// can be represented, including the ones before 1970.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp {
+    secs: i64,
+    nanos: u32,
+}
+
+impl Timestamp {
+    /// The Unix epoch, 1970-01-01 00:00:00 UTC.
+    pub const UNIX_EPOCH: Timestamp = Timestamp { secs: 0, nanos: 0 };
+
+    /// The earliest representable point in time.
+    pub const MIN: Timestamp = Timestamp {
+        secs: i64::MIN,
+        nanos: 0,
+    };
+
+    /// The latest representable point in time.
+    pub const MAX: Timestamp = Timestamp {
+        secs: i64::MAX,
+        nanos: NANOS_PER_SEC - 1,
+    };
+
+    /// Creates [`Timestamp`] from seconds and nanoseconds since the Unix epoch.
+    ///
+    /// Nanoseconds beyond one second are carried into `secs`, saturating to
+    /// [`Timestamp::MAX`].
+    pub const fn from_unix(secs: i64, nanos: u32) -> Self {
+        match secs.checked_add((nanos / NANOS_PER_SEC) as i64) {
+            Some(secs) => Self {
+                secs,
+                nanos: nanos % NANOS_PER_SEC,
+            },
+            None => Self::MAX,
+        }
+    }
+
+    /// Returns the current system time.
+    pub fn now() -> Self {
+        SystemTime::now().into()
+    }
+
+    /// Returns the whole seconds since the Unix epoch, negative before 1970.
+    pub const fn secs(&self) -> i64 {
+        self.secs
+    }
+
+    /// Returns the nanoseconds elapsed within the current second.
+    pub const fn subsec_nanos(&self) -> u32 {
+        self.nanos
+    }
+
+    /// Creates [`Timestamp`] from 100-ns intervals since the Gregorian epoch,
+    /// as stored by versions 1 and 6.
+    pub const fn from_gregorian(ticks: u64) -> Self {
+        Self {
+            secs: (ticks / 10_000_000) as i64 - GREGORIAN_OFFSET_SECS,
+            nanos: (ticks % 10_000_000) as u32 * 100,
+        }
+    }
+
+    /// Returns the count of 100-ns intervals since the Gregorian epoch,
+    /// saturating to zero before it.
+    pub const fn to_gregorian(&self) -> u64 {
+        let secs = self.secs.saturating_add(GREGORIAN_OFFSET_SECS);
+        if secs < 0 {
+            return 0;
+        }
+
+        (secs as u64)
+            .saturating_mul(10_000_000)
+            .saturating_add(self.nanos as u64 / 100)
+    }
+
+    /// Creates [`Timestamp`] from milliseconds since the Unix epoch, as stored by version 7.
+    pub const fn from_unix_millis(millis: u64) -> Self {
+        Self {
+            secs: (millis / 1_000) as i64,
+            nanos: (millis % 1_000) as u32 * 1_000_000,
+        }
+    }
+
+    /// Returns the milliseconds since the Unix epoch, saturating to zero before it.
+    pub const fn to_unix_millis(&self) -> u64 {
+        if self.secs < 0 {
+            return 0;
+        }
+
+        (self.secs as u64)
+            .saturating_mul(1_000)
+            .saturating_add(self.nanos as u64 / 1_000_000)
+    }
+
+    /// Returns `self + duration`, or `None` on overflow.
+    pub fn checked_add(&self, duration: Duration) -> Option<Self> {
+        let secs = i64::try_from(duration.as_secs()).ok()?;
+        let secs = self.secs.checked_add(secs)?;
+        let nanos = self.nanos + duration.subsec_nanos();
+
+        Some(Self {
+            secs: secs.checked_add((nanos / NANOS_PER_SEC) as i64)?,
+            nanos: nanos % NANOS_PER_SEC,
+        })
+    }
+
+    /// Returns `self - duration`, or `None` on overflow.
+    pub fn checked_sub(&self, duration: Duration) -> Option<Self> {
+        let secs = i64::try_from(duration.as_secs()).ok()?;
+        let mut secs = self.secs.checked_sub(secs)?;
+        let mut nanos = self.nanos;
+
+        if nanos < duration.subsec_nanos() {
+            secs = secs.checked_sub(1)?;
+            nanos += NANOS_PER_SEC;
+        }
+
+        Some(Self {
+            secs,
+            nanos: nanos - duration.subsec_nanos(),
+        })
+    }
+
+    /// Returns the time elapsed from `earlier` to `self`, or `None` if `earlier` is later.
+    pub fn checked_duration_since(&self, earlier: Timestamp) -> Option<Duration> {
+        if *self < earlier {
+            return None;
+        }
+
+        let (secs, nanos) = if self.nanos >= earlier.nanos {
+            (self.secs - earlier.secs, self.nanos - earlier.nanos)
+        } else {
+            (
+                self.secs - earlier.secs - 1,
+                self.nanos + NANOS_PER_SEC - earlier.nanos,
+            )
+        };
+
+        Some(Duration::new(secs as u64, nanos))
+    }
+}
+
+/// Returns the current system time, see [`Timestamp::now`].
+impl Default for Timestamp {
+    fn default() -> Self {
+        Self::now()
+    }
+}
+
+/// Saturates to [`Timestamp::MIN`] and [`Timestamp::MAX`] on platforms with a wider [`SystemTime`].
+impl From<SystemTime> for Timestamp {
+    fn from(time: SystemTime) -> Self {
+        match time.duration_since(UNIX_EPOCH) {
+            Ok(d) => match i64::try_from(d.as_secs()) {
+                Ok(secs) => Self::from_unix(secs, d.subsec_nanos()),
+                Err(_) => Self::MAX,
+            },
+            Err(err) => Self::UNIX_EPOCH
+                .checked_sub(err.duration())
+                .unwrap_or(Self::MIN),
+        }
+    }
+}
+
+/// Fails when the [`Timestamp`] is out of the range of the platform's [`SystemTime`].
+impl TryFrom<Timestamp> for SystemTime {
+    type Error = OutOfRangeError;
+
+    fn try_from(ts: Timestamp) -> Result<Self, OutOfRangeError> {
+        let nanos = Duration::from_nanos(ts.nanos as u64);
+        let time = if ts.secs >= 0 {
+            UNIX_EPOCH.checked_add(Duration::from_secs(ts.secs as u64))
+        } else {
+            UNIX_EPOCH.checked_sub(Duration::from_secs(ts.secs.unsigned_abs()))
+        };
+
+        time.and_then(|time| time.checked_add(nanos))
+            .ok_or(OutOfRangeError)
+    }
+}
+
+#[cfg(feature = "utc")]
+impl From<chrono::DateTime<Utc>> for Timestamp {
+    fn from(utc: chrono::DateTime<Utc>) -> Self {
+        Self::from_unix(utc.timestamp(), utc.timestamp_subsec_nanos())
+    }
+}
+
+/// Fails when the [`Timestamp`] is out of the range of [`chrono::DateTime`].
+#[cfg(feature = "utc")]
+impl TryFrom<Timestamp> for chrono::DateTime<Utc> {
+    type Error = OutOfRangeError;
+
+    fn try_from(ts: Timestamp) -> Result<Self, OutOfRangeError> {
+        chrono::DateTime::from_timestamp(ts.secs, ts.nanos).ok_or(OutOfRangeError)
+    }
+}
+
+/// Error returned when a [`Timestamp`] cannot be converted to another time type.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct OutOfRangeError;
+
+impl fmt::Display for OutOfRangeError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "timestamp out of range")
+    }
+}
+
+impl std::error::Error for OutOfRangeError {}
+
+impl Add<Duration> for Timestamp {
+    type Output = Timestamp;
+
+    fn add(self, duration: Duration) -> Timestamp {
+        self.checked_add(duration)
+            .expect("overflow when adding duration to timestamp")
+    }
+}
+
+impl AddAssign<Duration> for Timestamp {
+    fn add_assign(&mut self, duration: Duration) {
+        *self = *self + duration;
+    }
+}
+
+impl Sub<Duration> for Timestamp {
+    type Output = Timestamp;
+
+    fn sub(self, duration: Duration) -> Timestamp {
+        self.checked_sub(duration)
+            .expect("overflow when subtracting duration from timestamp")
+    }
+}
+
+impl SubAssign<Duration> for Timestamp {
+    fn sub_assign(&mut self, duration: Duration) {
+        *self = *self - duration;
+    }
+}
+
+/// Returns the time elapsed between two timestamps, saturating to zero.
+impl Sub<Timestamp> for Timestamp {
+    type Output = Duration;
+
+    fn sub(self, earlier: Timestamp) -> Duration {
+        self.checked_duration_since(earlier).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Number of 100-ns intervals between the Gregorian epoch and the Unix epoch.
+    const GREGORIAN_OFFSET: u64 = 0x01b2_1dd2_1381_4000;
+
+    // Tuesday, February 22, 2022 2:22:22.000000 PM GMT-05:00, see `rfc9562` appendix A.
+    const EXAMPLE: Timestamp = Timestamp::from_unix(1_645_557_742, 0);
+
+    #[test]
+    fn timestamp_from_unix() {
+        let ts = Timestamp::from_unix(1, 2_500_000_000);
+        assert_eq!((ts.secs(), ts.subsec_nanos()), (3, 500_000_000));
+        assert_eq!(Timestamp::from_unix(0, 0), Timestamp::UNIX_EPOCH);
+        assert_eq!(Timestamp::from_unix(i64::MAX, u32::MAX), Timestamp::MAX);
+        assert_eq!(Timestamp::from_unix(i64::MAX, 999_999_999), Timestamp::MAX);
+    }
+
+    #[test]
+    fn timestamp_gregorian_round_trip() {
+        assert_eq!(Timestamp::from_gregorian(0x1ec9414c232ab00), EXAMPLE);
+        assert_eq!(EXAMPLE.to_gregorian(), 0x1ec9414c232ab00);
+        assert_eq!(
+            Timestamp::from_gregorian(GREGORIAN_OFFSET),
+            Timestamp::UNIX_EPOCH
+        );
+
+        let ts = Timestamp::from_gregorian(0);
+        assert_eq!(ts.secs(), -GREGORIAN_OFFSET_SECS);
+        assert_eq!(ts.to_gregorian(), 0);
+        assert_eq!((ts - Duration::from_secs(1)).to_gregorian(), 0);
+
+        // Precision below 100 ns is dropped.
+        let ts = Timestamp::from_unix(0, 12_345);
+        assert_eq!(ts.to_gregorian(), GREGORIAN_OFFSET + 123);
+        assert_eq!(
+            Timestamp::from_gregorian(ts.to_gregorian()).subsec_nanos(),
+            12_300
+        );
+    }
+
+    #[test]
+    fn timestamp_unix_millis_round_trip() {
+        assert_eq!(Timestamp::from_unix_millis(0x017f22e279b0), EXAMPLE);
+        assert_eq!(EXAMPLE.to_unix_millis(), 0x017f22e279b0);
+        assert_eq!(Timestamp::from_unix(-1, 0).to_unix_millis(), 0);
+
+        let ts = Timestamp::from_unix_millis(1_234);
+        assert_eq!((ts.secs(), ts.subsec_nanos()), (1, 234_000_000));
+    }
+
+    #[test]
+    fn timestamp_system_time_round_trip() {
+        let now = SystemTime::now();
+        assert_eq!(SystemTime::try_from(Timestamp::from(now)), Ok(now));
+
+        let before = UNIX_EPOCH - Duration::new(1, 250_000_000);
+        let ts = Timestamp::from(before);
+        assert_eq!((ts.secs(), ts.subsec_nanos()), (-2, 750_000_000));
+        assert_eq!(SystemTime::try_from(ts), Ok(before));
+    }
+
+    #[test]
+    fn timestamp_out_of_system_time_range() {
+        // The range of `SystemTime` depends on the platform.
+        for ts in [
+            Timestamp::MIN,
+            Timestamp::from_unix(i64::MAX, 0),
+            Timestamp::MAX,
+        ] {
+            if let Ok(time) = SystemTime::try_from(ts) {
+                assert_eq!(Timestamp::from(time), ts);
+            }
+        }
+    }
+
+    #[test]
+    fn timestamp_arithmetic() {
+        let mut ts = Timestamp::from_unix(10, 900_000_000);
+        ts += Duration::from_millis(200);
+        assert_eq!(ts, Timestamp::from_unix(11, 100_000_000));
+        ts -= Duration::from_millis(200);
+        assert_eq!(ts, Timestamp::from_unix(10, 900_000_000));
+
+        let later = ts + Duration::new(5, 300_000_000);
+        assert_eq!(later - ts, Duration::new(5, 300_000_000));
+        assert_eq!(ts - later, Duration::ZERO);
+        assert_eq!(ts.checked_duration_since(later), None);
+
+        let max = Timestamp::from_unix(i64::MAX, 999_999_999);
+        assert_eq!(max.checked_add(Duration::from_nanos(1)), None);
+        assert_eq!(Timestamp::UNIX_EPOCH.checked_sub(Duration::MAX), None);
+    }
+
+    #[cfg(feature = "utc")]
+    #[test]
+    fn timestamp_utc_round_trip() {
+        let utc: chrono::DateTime<Utc> = EXAMPLE.try_into().unwrap();
+        assert_eq!(utc.to_rfc3339(), "2022-02-22T19:22:22+00:00");
+        assert_eq!(Timestamp::from(utc), EXAMPLE);
+
+        let ts = Timestamp::from_unix(i64::MAX, 0);
+        assert_eq!(chrono::DateTime::<Utc>::try_from(ts), Err(OutOfRangeError));
+    }
+}